@@ -0,0 +1,349 @@
+//! Expected answers recorded next to each input.
+//!
+//! `day1.txt` has its answers in `day1.answers`:
+//!
+//! ```text
+//! # Answers for day1.txt
+//! part1 = 514579
+//! part2 = hmac-sha256:5f0c6e3b9a3d4e21:0d6c...
+//! ```
+//!
+//! An answer is stored as plain text or as `hmac-sha256:<salt>:<digest>`,
+//! the HMAC-SHA256 of the salt followed by the answer under a key kept out of
+//! the repository: in `AOC_ANSWER_KEY`, or else in the git-ignored
+//! `.answer-key`. Without the key a keyed answer can't be checked, and can't
+//! be recovered by trying every candidate answer either.
+//!
+//! Older files may hold `sha256:<salt>:<digest>`, an unkeyed hash of the same
+//! string. Those are still checked, but hide nothing: puzzle answers are
+//! small enough to find by hashing every candidate with the salt.
+
+use std::collections::hash_map::RandomState;
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::{self, Display};
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::error::{span_of, Error, Result};
+use crate::fixtures;
+use crate::input::Input;
+use crate::runner;
+use crate::sha256;
+use crate::solution::{Answer, Solver};
+
+const HASH_PREFIX: &str = "sha256:";
+const KEYED_PREFIX: &str = "hmac-sha256:";
+
+/// Environment variable holding the answer key.
+pub const KEY_VAR: &str = "AOC_ANSWER_KEY";
+/// File holding the answer key when `KEY_VAR` isn't set.
+pub const KEY_FILE: &str = ".answer-key";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expected {
+    Plain(String),
+    /// Unkeyed, from older answer files.
+    Hashed { salt: String, digest: String },
+    Keyed { salt: String, digest: String },
+}
+
+impl Expected {
+    pub fn plain(answer: &Answer) -> Self {
+        Expected::Plain(answer.to_string())
+    }
+
+    pub fn keyed(answer: &Answer, key: &[u8]) -> Self {
+        let salt = new_salt();
+        let digest = sha256::hex_hmac(key, salted(&salt, answer).as_bytes());
+        Expected::Keyed { salt, digest }
+    }
+
+    /// Whether `answer` is the expected one, or `None` for a keyed answer
+    /// when there's no key to check it with.
+    pub fn check(&self, answer: &Answer, key: Option<&[u8]>) -> Option<bool> {
+        match self {
+            Expected::Plain(expected) => Some(*expected == answer.to_string()),
+            Expected::Hashed { salt, digest } => Some(*digest == sha256::hex_digest(salted(salt, answer).as_bytes())),
+            Expected::Keyed { salt, digest } => {
+                key.map(|key| *digest == sha256::hex_hmac(key, salted(salt, answer).as_bytes()))
+            }
+        }
+    }
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Plain(answer) => write!(f, "{}", answer),
+            Expected::Hashed { salt, digest } => write!(f, "{}{}:{}", HASH_PREFIX, salt, digest),
+            Expected::Keyed { salt, digest } => write!(f, "{}{}:{}", KEYED_PREFIX, salt, digest),
+        }
+    }
+}
+
+impl std::str::FromStr for Expected {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (prefix, hashed) = match [KEYED_PREFIX, HASH_PREFIX]
+            .into_iter()
+            .find_map(|prefix| Some((prefix, s.strip_prefix(prefix)?)))
+        {
+            Some(found) => found,
+            None => return Ok(Expected::Plain(s.to_string())),
+        };
+
+        let (salt, digest) = hashed
+            .split_once(':')
+            .ok_or_else(|| Error::new(format!("Expected `{}<salt>:<digest>`", prefix)))?;
+        if digest.len() != 64 || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(Error::new("Expected a 64 digit hex SHA-256 digest"));
+        }
+
+        let (salt, digest) = (salt.to_string(), digest.to_lowercase());
+        if prefix == KEYED_PREFIX {
+            Ok(Expected::Keyed { salt, digest })
+        } else {
+            Ok(Expected::Hashed { salt, digest })
+        }
+    }
+}
+
+fn salted(salt: &str, answer: &Answer) -> String {
+    format!("{}{}", salt, answer)
+}
+
+/// The answer key from `KEY_VAR` or `KEY_FILE`, if either is set.
+pub fn answer_key() -> Result<Option<Vec<u8>>> {
+    if let Some(key) = env::var_os(KEY_VAR).filter(|key| !key.is_empty()) {
+        return Ok(Some(key.to_string_lossy().into_owned().into_bytes()));
+    }
+
+    match fs::read_to_string(KEY_FILE) {
+        Ok(text) if text.trim().is_empty() => Ok(None),
+        Ok(text) => Ok(Some(text.trim().as_bytes().to_vec())),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(Error::new(format!("Failed to read {}", KEY_FILE)).caused_by(err)),
+    }
+}
+
+fn new_salt() -> String {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u128(nanos);
+    format!("{:016x}", hasher.finish())
+}
+
+/// The expected answers for one input file.
+#[derive(Debug, Default, Clone)]
+pub struct Answers {
+    parts: BTreeMap<u8, Expected>,
+}
+
+impl Answers {
+    pub fn get(&self, part: u8) -> Option<&Expected> {
+        self.parts.get(&part)
+    }
+
+    pub fn set(&mut self, part: u8, expected: Expected) {
+        self.parts.insert(part, expected);
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut answers = Answers::default();
+
+        for (index, text) in text.lines().enumerate() {
+            let line = text.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = |part: &str, message: &str| {
+                Error::at_line(message, index, text).with_span(span_of(text, part))
+            };
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| invalid(line, "Expected `partN = <answer>`"))?;
+            let key = key.trim();
+            let part = key
+                .strip_prefix("part")
+                .and_then(|part| part.parse::<u8>().ok())
+                .ok_or_else(|| invalid(key, "Expected `part1` or `part2`"))?;
+
+            let value = value.trim();
+            let expected = value.parse().map_err(|err: Error| err.with_span(span_of(text, value)).with_line(index, text))?;
+            answers.set(part, expected);
+        }
+
+        Ok(answers)
+    }
+
+    /// Loads the answers for `input`. A missing answers file means every
+    /// answer is unknown.
+    pub fn load(input: &Path) -> Result<Self> {
+        let path = answers_path(input);
+        match fs::read_to_string(&path) {
+            Ok(text) => Answers::parse(&text).map_err(|err| err.in_file(&path)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(err) => Err(Error::new(format!("Failed to read {}", path.display())).caused_by(err)),
+        }
+    }
+
+    pub fn save(&self, input: &Path) -> Result<()> {
+        let path = answers_path(input);
+        let mut text = format!("# Answers for {}\n", input.display());
+        for (part, expected) in self.parts.iter() {
+            text.push_str(&format!("part{} = {}\n", part, expected));
+        }
+
+        fs::write(&path, text)
+            .map_err(|err| Error::new(format!("Failed to write {}", path.display())).caused_by(err))
+    }
+}
+
+/// `day1.txt` -> `day1.answers`.
+pub fn answers_path(input: &Path) -> PathBuf {
+    input.with_extension("answers")
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail(Answer),
+    Unknown(Answer),
+    Error(String),
+}
+
+impl Verdict {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Fail(_) | Verdict::Error(_))
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail(answer) => write!(f, "FAIL (got {})", answer),
+            Verdict::Unknown(answer) => write!(f, "unknown (got {})", answer),
+            Verdict::Error(err) => write!(f, "ERROR ({})", err),
+        }
+    }
+}
+
+/// Inputs in the current directory with recorded or recordable answers for
+/// `day`: the real input, if there is one, and every example fixture.
+pub fn inputs(day: u32) -> Result<Vec<PathBuf>> {
+    let mut inputs = vec![];
+
+    let real = PathBuf::from(format!("day{}.txt", day));
+    if real.is_file() {
+        inputs.push(real);
+    }
+
+    for fixture in fixtures::discover(Path::new("."))? {
+        if fixture.day == day {
+            let path = fixture.path.strip_prefix(".").map(Path::to_path_buf).unwrap_or(fixture.path);
+            inputs.push(path);
+        }
+    }
+
+    Ok(inputs)
+}
+
+/// Runs every part of `solver` on `input` and checks it against the
+/// recorded answers. Keyed answers are unknown without the answer key.
+pub fn verify(solver: &dyn Solver, input: &Path) -> Result<Vec<(u8, Verdict)>> {
+    let answers = Answers::load(input)?;
+    let key = answer_key()?;
+    let input = Input::from_path(input)?;
+
+    let parsed = match runner::parse_input(solver, &input) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            let verdict = Verdict::Error(err.to_string());
+            return Ok(runner::parts(solver, None).into_iter().map(|part| (part, verdict.clone())).collect());
+        }
+    };
+
+    let verdicts = runner::parts(solver, None)
+        .into_iter()
+        .map(|part| {
+            let verdict = match (runner::solve_part(solver, parsed.as_ref(), part), answers.get(part)) {
+                (Err(err), _) => Verdict::Error(input.locate(err).to_string()),
+                (Ok(answer), None) => Verdict::Unknown(answer),
+                (Ok(answer), Some(expected)) => match expected.check(&answer, key.as_deref()) {
+                    Some(true) => Verdict::Pass,
+                    Some(false) => Verdict::Fail(answer),
+                    None => Verdict::Unknown(answer),
+                },
+            };
+            (part, verdict)
+        })
+        .collect();
+
+    Ok(verdicts)
+}
+
+/// Solves `input` and writes its answers file, keyed unless `plain`.
+pub fn record(solver: &dyn Solver, input: &Path, plain: bool) -> Result<Answers> {
+    let key = if plain {
+        None
+    } else {
+        let missing = || {
+            Error::new(format!("Recording hashed answers needs a key in {} or {}, or use --plain", KEY_VAR, KEY_FILE))
+        };
+        Some(answer_key()?.ok_or_else(missing)?)
+    };
+    let loaded = Input::from_path(input)?;
+    let parsed = runner::parse_input(solver, &loaded)?;
+
+    let mut answers = Answers::default();
+    for part in runner::parts(solver, None) {
+        let answer = runner::solve_part(solver, parsed.as_ref(), part).map_err(|err| loaded.locate(err))?;
+        let expected = match &key {
+            Some(key) => Expected::keyed(&answer, key),
+            None => Expected::plain(&answer),
+        };
+        answers.set(part, expected);
+    }
+
+    answers.save(input)?;
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyed_answers_need_the_key() {
+        let answer = Answer::from(514579);
+        let expected = Expected::keyed(&answer, b"secret");
+        let parsed: Expected = expected.to_string().parse().unwrap();
+
+        assert_eq!(parsed, expected);
+        assert_eq!(parsed.check(&answer, Some(b"secret")), Some(true));
+        assert_eq!(parsed.check(&Answer::from(514578), Some(b"secret")), Some(false));
+        assert_eq!(parsed.check(&answer, Some(b"guess")), Some(false));
+        assert_eq!(parsed.check(&answer, None), None);
+    }
+
+    #[test]
+    fn unkeyed_hashes_still_check() {
+        let salt = "5f0c6e3b9a3d4e21";
+        let digest = sha256::hex_digest(b"5f0c6e3b9a3d4e21514579");
+        let expected: Expected = format!("sha256:{}:{}", salt, digest).parse().unwrap();
+
+        assert_eq!(expected.check(&Answer::from(514579), None), Some(true));
+        assert_eq!(expected.check(&Answer::from(1), None), Some(false));
+    }
+}
@@ -0,0 +1,146 @@
+//! The command line parser shared by `aoc` and the per-day binaries.
+//!
+//! Options are `--name value` or `--name=value`, flags are a bare `--name`,
+//! and everything else is positional. A command takes what it knows about
+//! and then calls [`Args::finish`], so anything left over, an unknown option
+//! included, is an error rather than being ignored.
+
+use std::env;
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+
+#[derive(Debug, Clone)]
+pub struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    pub fn new(args: Vec<String>) -> Self {
+        Args { args }
+    }
+
+    /// The arguments the program was run with, without its name.
+    pub fn from_env() -> Self {
+        Args::new(env::args().skip(1).collect())
+    }
+
+    pub fn option(&mut self, name: &str) -> Result<Option<String>> {
+        let flag = format!("--{}", name);
+        let prefix = format!("--{}=", name);
+
+        for i in 0..self.args.len() {
+            if self.args[i] == flag {
+                if i + 1 >= self.args.len() {
+                    return Err(Error::new(format!("Missing value for {}", flag)));
+                }
+                let value = self.args.remove(i + 1);
+                self.args.remove(i);
+                return Ok(Some(value));
+            }
+            if let Some(value) = self.args[i].strip_prefix(&prefix) {
+                let value = value.to_string();
+                self.args.remove(i);
+                return Ok(Some(value));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Every value of an option that can be given more than once, in order.
+    pub fn options(&mut self, name: &str) -> Result<Vec<String>> {
+        let mut values = vec![];
+        while let Some(value) = self.option(name)? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+
+    /// An option parsed as a `T`, or `default` if it isn't given.
+    pub fn parse_or<T: FromStr>(&mut self, name: &str, default: T) -> Result<T> {
+        match self.option(name)? {
+            Some(value) => value
+                .parse()
+                .map_err(|_| Error::new(format!("Invalid value for --{}: {}", name, value))),
+            None => Ok(default),
+        }
+    }
+
+    /// The first argument, if it isn't an option.
+    pub fn command(&mut self) -> Option<String> {
+        match self.args.first() {
+            Some(arg) if !arg.starts_with("--") => Some(self.args.remove(0)),
+            _ => None,
+        }
+    }
+
+    pub fn flag(&mut self, name: &str) -> bool {
+        let flag = format!("--{}", name);
+        match self.args.iter().position(|arg| *arg == flag) {
+            Some(index) => {
+                self.args.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn positional(&mut self) -> Option<String> {
+        let index = self.args.iter().position(|arg| !arg.starts_with("--"))?;
+        Some(self.args.remove(index))
+    }
+
+    /// Fails on the first argument nothing asked for.
+    pub fn finish(self) -> Result<()> {
+        match self.args.first() {
+            Some(arg) if arg.starts_with("--") => Err(Error::new(format!("Unknown option: {}", arg))),
+            Some(arg) => Err(Error::new(format!("Unexpected argument: {}", arg))),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Args {
+        Args::new(args.iter().map(|arg| arg.to_string()).collect())
+    }
+
+    #[test]
+    fn takes_options_in_either_form() {
+        let mut args = args(&["run", "--part=2", "3", "--input", "-", "--plain"]);
+        assert_eq!(args.command().as_deref(), Some("run"));
+        assert_eq!(args.option("input").unwrap().as_deref(), Some("-"));
+        assert_eq!(args.parse_or("part", 1).unwrap(), 2);
+        assert!(args.flag("plain"));
+        assert!(!args.flag("record"));
+        assert_eq!(args.positional().as_deref(), Some("3"));
+        assert!(args.finish().is_ok());
+    }
+
+    #[test]
+    fn collects_repeated_options() {
+        let mut args = args(&["--policy", "a", "--workers", "2", "--policy=b"]);
+        assert_eq!(args.options("policy").unwrap(), vec!["a", "b"]);
+        assert_eq!(args.parse_or("workers", 1).unwrap(), 2);
+        assert_eq!(args.parse_or("chunk", 7).unwrap(), 7);
+        assert!(args.finish().is_ok());
+    }
+
+    #[test]
+    fn rejects_what_nothing_asked_for() {
+        let mut unknown = args(&["--lenient", "--bogus", "1"]);
+        assert!(unknown.flag("lenient"));
+        assert_eq!(unknown.finish().unwrap_err().message(), "Unknown option: --bogus");
+
+        assert_eq!(args(&["extra"]).finish().unwrap_err().message(), "Unexpected argument: extra");
+        assert_eq!(args(&["--target"]).option("target").unwrap_err().message(), "Missing value for --target");
+        assert_eq!(
+            args(&["--budget", "lots"]).parse_or("budget", 0usize).unwrap_err().message(),
+            "Invalid value for --budget: lots"
+        );
+    }
+}
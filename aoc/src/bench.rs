@@ -0,0 +1,140 @@
+//! Timing of the parse, part1 and part2 phases of a solver.
+
+use std::fmt::{self, Display};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::json;
+use crate::runner;
+use crate::solution::Solver;
+
+#[derive(Debug, Clone, Copy)]
+pub struct BenchOptions {
+    /// Timed runs per phase.
+    pub iterations: usize,
+    /// Untimed runs per phase before timing starts.
+    pub warmup: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions { iterations: 100, warmup: 10 }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part(u8),
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part(part) => write!(f, "part{}", part),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    fn from_samples(mut samples: Vec<Duration>) -> Result<Self> {
+        if samples.is_empty() {
+            return Err(Error::new("No samples to summarise"));
+        }
+        samples.sort();
+
+        // Nearest-rank percentile.
+        let percentile = |p: usize| samples[(samples.len() * p).div_ceil(100).max(1) - 1];
+
+        Ok(Stats {
+            samples: samples.len(),
+            min: samples[0],
+            median: percentile(50),
+            p95: percentile(95),
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    pub day: u32,
+    pub input: String,
+    pub phases: Vec<(Phase, Stats)>,
+}
+
+fn time<T>(options: &BenchOptions, mut f: impl FnMut() -> Result<T>) -> Result<Stats> {
+    for _ in 0..options.warmup {
+        black_box(f()?);
+    }
+
+    let mut samples = Vec::with_capacity(options.iterations);
+    for _ in 0..options.iterations {
+        let start = Instant::now();
+        let result = f()?;
+        samples.push(start.elapsed());
+        black_box(result);
+    }
+
+    Stats::from_samples(samples)
+}
+
+/// Times each phase of `solver` on `input`. The parts are timed against a
+/// single parsed input so their numbers exclude parsing.
+pub fn bench(solver: &dyn Solver, input: &Input, options: &BenchOptions) -> Result<Report> {
+    let parsed = runner::parse_input(solver, input)?;
+    let mut phases = vec![(Phase::Parse, time(options, || solver.parse(input.lines()))?)];
+
+    for part in runner::parts(solver, None) {
+        let stats = time(options, || runner::solve_part(solver, parsed.as_ref(), part)).map_err(|err| input.locate(err))?;
+        phases.push((Phase::Part(part), stats));
+    }
+
+    Ok(Report { day: solver.day(), input: input.name(), phases })
+}
+
+pub fn to_json(reports: &[Report], options: &BenchOptions) -> String {
+    let results = reports
+        .iter()
+        .map(|report| {
+            let phases = report
+                .phases
+                .iter()
+                .map(|(phase, stats)| {
+                    format!(
+                        "{{\"phase\":{},\"samples\":{},\"min_ns\":{},\"median_ns\":{},\"p95_ns\":{}}}",
+                        json::quote(&phase.to_string()),
+                        stats.samples,
+                        stats.min.as_nanos(),
+                        stats.median.as_nanos(),
+                        stats.p95.as_nanos(),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            format!(
+                "{{\"day\":{},\"input\":{},\"phases\":[{}]}}",
+                report.day,
+                json::quote(&report.input),
+                phases.join(","),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    format!(
+        "{{\"iterations\":{},\"warmup\":{},\"results\":[{}]}}\n",
+        options.iterations,
+        options.warmup,
+        results.join(","),
+    )
+}
@@ -0,0 +1,81 @@
+use std::process;
+
+use aoc::args::Args;
+use aoc::day1::{self, Day1, Expenses, Nearest};
+use aoc::error::{Error, Result};
+use aoc::input::Input;
+
+const USAGE: &str = "\
+Usage: day1 [--nearest | subset [--target N] [--budget N]] < input
+
+With no arguments, solves both parts. `--nearest` also names the closest
+combination when a part has no answer, which takes O(n²) for part 2. `subset` counts the non-empty subsets
+of entries, of any size, that sum to the target (default 2020) and prints one
+of the smallest. It gives up after `budget` steps (default 50000000).";
+
+fn nearest() -> Result<()> {
+    let input = Input::from_stdin()?;
+    let expenses = Expenses::parse(input.lines()).map_err(|err| err.in_file(input.name()))?;
+
+    for (part, k) in [(1, 2), (2, 3)] {
+        let answer = expenses.solve_k_sum(k, Some(Nearest::Either)).map_err(|err| err.in_file(input.name()))?;
+        println!("Part{}: {}", part, answer);
+    }
+    Ok(())
+}
+
+fn subset(mut args: Args) -> Result<()> {
+    let target = args.parse_or("target", 2020i128)?;
+    let budget = args.parse_or("budget", day1::DEFAULT_SUBSET_BUDGET)?;
+    args.finish()?;
+
+    let input = Input::from_stdin()?;
+    let numbers = day1::parse_numbers::<i128>(input.lines()).map_err(|err| err.in_file(input.name()))?;
+    let found = day1::subset_sum(&numbers, target, budget)?;
+
+    println!("subsets: {}", found.count);
+    match found.smallest {
+        Some(entries) => {
+            let noun = if entries.len() == 1 { "entry" } else { "entries" };
+            println!("smallest ({} {}):", entries.len(), noun);
+            for entry in entries {
+                println!("    {}", entry);
+            }
+        }
+        None => println!("no subset sums to {}", target),
+    }
+
+    Ok(())
+}
+
+fn run(mut args: Args) -> Result<()> {
+    if args.flag("help") {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+
+    match args.command().as_deref() {
+        None => {
+            let nearest = args.flag("nearest");
+            args.finish()?;
+            if nearest {
+                self::nearest()
+            } else {
+                aoc::runner::run::<Day1>()
+            }
+        }
+        Some("subset") => subset(args),
+        Some("help") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some(command) => Err(Error::new(format!("Unknown command: {}\n\n{}", command, USAGE))),
+    }
+}
+
+fn main() {
+    if let Err(err) = run(Args::from_env()) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,134 @@
+use std::io::{self, BufWriter};
+use std::process;
+use std::thread;
+
+use aoc::args::Args;
+use aoc::day2::{self, AuditFormat, Day2, Keep, OnError, PasswordPolicy, Policies};
+use aoc::error::{Error, Result};
+use aoc::input::{Input, Source};
+
+const USAGE: &str = "\
+Usage: day2 [--policy NAME] < input
+       day2 audit [--format table|csv|json] [--policy NAME]... < input
+       day2 stream [--policy NAME]... [--workers N] [--chunk N] < input
+       day2 filter [--policy NAME] [--keep pass|fail] [--on-error skip|warn|fail] < input
+
+With no arguments, solves both parts. `--policy` counts the lines valid under
+just that policy: sled-rental or toboggan.
+
+`audit` reports every line: what it parsed to, whether each policy passes
+it, and why. It checks every policy unless some are picked with `--policy`.
+
+`stream` counts the valid lines under each policy without loading the whole
+input: lines are read in chunks (default 4096 lines) and checked on a pool of
+worker threads (default one per CPU).
+
+`filter` copies the lines that pass one policy (default sled-rental), or with
+`--keep fail` the lines that fail it, to stdout as it reads them. Lines that
+don't parse or can't be checked are never copied: by default they are skipped
+with a warning, `--on-error skip` drops them silently and `--on-error fail`
+stops at the first one. It exits with 0 if every line passed, 1 if any didn't
+and 2 on any error, a bad command line included.";
+
+fn count(policies: &Policies, policy: &str) -> Result<()> {
+    let policy = policies.find(policy)?;
+
+    let input = Input::from_stdin()?;
+    let lines = day2::parse_lines(input.lines()).map_err(|err| input.locate(err))?;
+
+    let count = day2::count_valid(policy, &lines).map_err(|err| input.locate(err))?;
+    println!("{}: {}", policy.name(), count);
+    Ok(())
+}
+
+/// The policies named with `--policy`, or all of them if none are.
+fn pick<'a>(policies: &'a Policies, args: &mut Args) -> Result<Vec<&'a dyn PasswordPolicy>> {
+    let names = args.options("policy")?;
+    if names.is_empty() {
+        return Ok(policies.names().into_iter().filter_map(|name| policies.get(name)).collect());
+    }
+    names.iter().map(|name| policies.find(name)).collect()
+}
+
+fn audit(policies: &Policies, mut args: Args) -> Result<()> {
+    let format = args.option("format")?.map(|format| format.parse()).transpose()?.unwrap_or(AuditFormat::Table);
+    let picked = pick(policies, &mut args)?;
+    args.finish()?;
+
+    let input = Input::from_stdin()?;
+    let rows = day2::audit(&picked, input.lines());
+    print!("{}", day2::render_audit(&picked, &rows, format));
+    Ok(())
+}
+
+fn stream(policies: &Policies, mut args: Args) -> Result<()> {
+    let workers = args.parse_or("workers", thread::available_parallelism().map_or(1, |n| n.get()))?;
+    let chunk = args.parse_or("chunk", day2::STREAM_CHUNK)?;
+    let picked = pick(policies, &mut args)?;
+    args.finish()?;
+
+    let stdin = io::stdin();
+    let totals = day2::validate_stream(stdin.lock(), &picked, workers, chunk).map_err(|err| err.in_file(Source::Stdin.to_string()))?;
+    println!("lines: {}", totals.lines);
+    for (policy, valid) in picked.iter().zip(totals.valid) {
+        println!("{}: {}", policy.name(), valid);
+    }
+    Ok(())
+}
+
+/// Whether every line passed.
+fn filter(policies: &Policies, mut args: Args) -> Result<bool> {
+    let policy = policies.find(&args.option("policy")?.unwrap_or_else(|| "sled-rental".to_string()))?;
+    let keep = args.option("keep")?.map(|keep| keep.parse()).transpose()?.unwrap_or(Keep::Passing);
+    let on_error = args.option("on-error")?.map(|action| action.parse()).transpose()?.unwrap_or(OnError::Warn);
+    args.finish()?;
+
+    let name = Source::Stdin.to_string();
+    let stdin = io::stdin();
+    let mut out = BufWriter::new(io::stdout().lock());
+    let warn = |err: Error| eprintln!("warning: {}", err.in_file(&name));
+    day2::filter_lines(stdin.lock(), &mut out, policy, keep, on_error, warn).map_err(|err| err.in_file(&name))
+}
+
+fn run(mut args: Args) -> Result<()> {
+    let policies = Policies::default();
+    if args.flag("help") {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+
+    match args.command().as_deref() {
+        None => {
+            let policy = args.option("policy")?;
+            args.finish()?;
+            match policy {
+                Some(policy) => count(&policies, &policy),
+                None => aoc::runner::run::<Day2>(),
+            }
+        }
+        Some("audit") => audit(&policies, args),
+        Some("stream") => stream(&policies, args),
+        Some("filter") => {
+            if !filter(&policies, args)? {
+                process::exit(1);
+            }
+            Ok(())
+        }
+        Some("help") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some(command) => Err(Error::new(format!("Unknown command: {}\n\n{}", command, USAGE))),
+    }
+}
+
+fn main() {
+    let args = Args::from_env();
+    // `filter` keeps 1 for lines that didn't pass, so its errors, usage
+    // errors included, get 2.
+    let filtering = args.clone().command().is_some_and(|command| command == "filter");
+    if let Err(err) = run(args) {
+        eprintln!("error: {}", err);
+        process::exit(if filtering { 2 } else { 1 });
+    }
+}
@@ -0,0 +1,11 @@
+use std::process;
+
+use aoc::args::Args;
+use aoc::day24::Day24;
+
+fn main() {
+    if let Err(err) = Args::from_env().finish().and_then(|()| aoc::runner::run::<Day24>()) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,11 @@
+use std::process;
+
+use aoc::args::Args;
+use aoc::day25::Day25;
+
+fn main() {
+    if let Err(err) = Args::from_env().finish().and_then(|()| aoc::runner::run::<Day25>()) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
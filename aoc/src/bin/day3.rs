@@ -0,0 +1,96 @@
+use std::process;
+use std::thread;
+
+use aoc::args::Args;
+use aoc::day3::{self, Day3, Slope, SlopeSpace, TreeMap};
+use aoc::error::{Error, Result};
+use aoc::input::Input;
+use aoc::Solution;
+
+const USAGE: &str = "\
+Usage: day3 [--lenient] < input
+       day3 slopes [--max-right N] [--max-down N] [--rational] [--lenient] < input
+
+With no arguments, solves both parts. The map must be rows of `.` and `#`, all
+the same width. `--lenient` accepts any map instead: shorter rows are padded
+to the width of the widest, and anything but `#` is open ground.
+
+`slopes` counts the trees on every slope going up to `max-right` (default 10)
+right and `max-down` (default 5) down per step, and ranks them from fewest
+trees to most. `--rational` tries each direction once, in lowest terms, so
+`right 2, down 2` is left out as the same direction as `right 1, down 1`.";
+
+fn read_map(lenient: bool) -> Result<TreeMap> {
+    let input = Input::from_stdin()?;
+    if lenient {
+        return Ok(TreeMap::lenient(input.lines()));
+    }
+    TreeMap::new(input.lines()).map_err(|err| input.locate(err))
+}
+
+fn slopes(mut args: Args) -> Result<()> {
+    let max_right = args.parse_or("max-right", 10)?;
+    let max_down = args.parse_or("max-down", 5)?;
+    let space = if args.flag("rational") {
+        SlopeSpace::Rational { max_right, max_down }
+    } else {
+        SlopeSpace::Grid { max_right, max_down }
+    };
+    let lenient = args.flag("lenient");
+    args.finish()?;
+
+    let map = read_map(lenient)?;
+    let workers = thread::available_parallelism().map_or(1, |n| n.get());
+    let ranking = day3::search_slopes(&map, space, workers);
+
+    let list = |slopes: &[(Slope, usize)]| slopes.iter().map(|(slope, _)| slope.to_string()).collect::<Vec<_>>().join("; ");
+    if let (Some((_, fewest)), Some((_, most))) = (ranking.fewest().first(), ranking.most().first()) {
+        println!("fewest trees ({}): {}", fewest, list(ranking.fewest()));
+        println!("most trees ({}): {}", most, list(ranking.most()));
+    }
+    println!("ranking:");
+    for (rank, (slope, trees)) in ranking.ranked.iter().enumerate() {
+        println!("{:>5}. {:>6} trees  {}", rank + 1, trees, slope);
+    }
+    Ok(())
+}
+
+fn lenient() -> Result<()> {
+    let map = read_map(true)?;
+
+    println!("Part1: {}", Day3::part1(&map)?);
+    println!("Part2: {}", Day3::part2(&map)?);
+    Ok(())
+}
+
+fn run(mut args: Args) -> Result<()> {
+    if args.flag("help") {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+
+    match args.command().as_deref() {
+        None => {
+            let lenient = args.flag("lenient");
+            args.finish()?;
+            if lenient {
+                self::lenient()
+            } else {
+                aoc::runner::run::<Day3>()
+            }
+        }
+        Some("slopes") => slopes(args),
+        Some("help") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some(command) => Err(Error::new(format!("Unknown command: {}\n\n{}", command, USAGE))),
+    }
+}
+
+fn main() {
+    if let Err(err) = run(Args::from_env()) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,11 @@
+use std::process;
+
+use aoc::args::Args;
+use aoc::day4::Day4;
+
+fn main() {
+    if let Err(err) = Args::from_env().finish().and_then(|()| aoc::runner::run::<Day4>()) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,998 @@
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Display};
+use std::hash::Hash;
+use std::iter;
+use std::num::{IntErrorKind, ParseIntError};
+use std::ops::ControlFlow;
+use std::str::FromStr;
+
+use crate::diff::Implementation;
+use crate::error::{self, Error};
+use crate::generate::Generated;
+use crate::rng::Rng;
+use crate::solution::{Answer, Solution};
+
+/// An integer type an expense report can be held in. Sums and products are
+/// worked out in `i128`, so a match is found whatever width the entries are
+/// stored at.
+pub trait Expense: Copy + Eq + Ord + Hash + Debug + Display + From<i32> + FromStr<Err = ParseIntError> + 'static {
+    const NAME: &'static str;
+
+    fn wide(self) -> i128;
+}
+
+macro_rules! expense {
+    ($($t:ty),+) => {
+        $(impl Expense for $t {
+            const NAME: &'static str = stringify!($t);
+
+            fn wide(self) -> i128 {
+                self as i128
+            }
+        })+
+    };
+}
+
+expense!(i32, i64, i128);
+
+pub fn parse_numbers<T: Expense>(lines: &[String]) -> Result<Vec<T>, Error> {
+
+    lines.iter().enumerate().map(|(index, x)| {
+        x.parse::<T>().map_err(|err| {
+            let message = match err.kind() {
+                IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => format!("Number doesn't fit in {}: {}", T::NAME, x),
+                _ => format!("Invalid number: {}", x),
+            };
+            Error::at_line(message, index, x)
+                .with_span(0..x.chars().count())
+                .caused_by(err)
+        })
+    }).collect()
+
+    /*
+    let mut numbers: Vec<i32> = Vec::new();
+    for line in lines.iter() {
+        let num = line.parse::<i32>().unwrap();
+        numbers.push(num);
+    }
+    //println!("{:?}", numbers);
+    numbers
+    */
+}
+
+/// An expense report in the narrowest integer type that holds every entry.
+#[derive(Debug, Clone)]
+pub enum Expenses {
+    I32(Vec<i32>),
+    I64(Vec<i64>),
+    I128(Vec<i128>),
+}
+
+impl Expenses {
+    pub fn parse(lines: &[String]) -> Result<Self, Error> {
+        let numbers = parse_numbers::<i128>(lines)?;
+
+        if let Ok(numbers) = numbers.iter().map(|n| i32::try_from(*n)).collect() {
+            return Ok(Expenses::I32(numbers));
+        }
+        if let Ok(numbers) = numbers.iter().map(|n| i64::try_from(*n)).collect() {
+            return Ok(Expenses::I64(numbers));
+        }
+        Ok(Expenses::I128(numbers))
+    }
+
+    /// Name of the type the entries are held in.
+    pub fn width(&self) -> &'static str {
+        match self {
+            Expenses::I32(_) => i32::NAME,
+            Expenses::I64(_) => i64::NAME,
+            Expenses::I128(_) => i128::NAME,
+        }
+    }
+
+    /// [`solve_k_sum`] at whatever width the entries are held in.
+    pub fn solve_k_sum(&self, k: usize, nearest: Option<Nearest>) -> Result<Answer, Error> {
+        match self {
+            Expenses::I32(numbers) => solve_k_sum(numbers, k, nearest),
+            Expenses::I64(numbers) => solve_k_sum(numbers, k, nearest),
+            Expenses::I128(numbers) => solve_k_sum(numbers, k, nearest),
+        }
+    }
+}
+
+/// Product of `values`, or an error naming them if it overflows `i128`.
+pub fn product<T: Expense>(values: &[T]) -> Result<Answer, Error> {
+    values
+        .iter()
+        .try_fold(1i128, |product, value| product.checked_mul(value.wide()))
+        .map(Answer::from)
+        .ok_or_else(|| {
+            let values = values.iter().map(|value| value.to_string()).collect::<Vec<_>>();
+            Error::new(format!("Product of {} overflows i128", values.join(" * ")))
+        })
+}
+
+/// An entry of the expense report: its value and the index of the line it
+/// came from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Entry<T> {
+    pub value: T,
+    pub index: usize,
+}
+
+impl<T: Display> Display for Entry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (line {})", self.value, self.index + 1)
+    }
+}
+
+/// The entries at `positions`.
+pub fn entries<T: Expense>(numbers: &[T], positions: &[usize]) -> Vec<Entry<T>> {
+    positions.iter().map(|index| Entry { value: numbers[*index], index: *index }).collect()
+}
+
+/// Which side of the target a nearest combination may fall on.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Nearest {
+    Either,
+    Below,
+    Above,
+}
+
+/// No `k` entries sum to `target`. `nearest` is the combination that comes
+/// closest, when asked for with [`NoSolution::with_nearest`].
+#[derive(Debug, Clone)]
+pub struct NoSolution<T> {
+    pub k: usize,
+    pub target: T,
+    pub nearest: Option<Vec<Entry<T>>>,
+}
+
+impl<T: Expense> NoSolution<T> {
+    pub fn new(k: usize, target: T) -> Self {
+        NoSolution { k, target, nearest: None }
+    }
+
+    /// Looks up the combination closest to the target on the `side` given.
+    pub fn with_nearest(self, numbers: &[T], side: Nearest) -> Self {
+        let nearest = nearest_k_sum(numbers, self.k, self.target, side);
+        NoSolution { nearest, ..self }
+    }
+}
+
+impl<T: Expense> Display for NoSolution<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "No {} entries sum to {}", self.k, self.target)?;
+
+        if let Some(nearest) = &self.nearest {
+            let sum = nearest.iter().map(|entry| entry.value.wide()).sum::<i128>();
+            let terms = nearest.iter().map(|entry| entry.to_string()).collect::<Vec<_>>();
+            let off = sum - self.target.wide();
+            let side = if off < 0 { "below" } else { "above" };
+            write!(f, "; closest is {} = {}, {} {}", terms.join(" + "), sum, off.abs(), side)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Expense> std::error::Error for NoSolution<T> {}
+
+impl<T: Expense> From<NoSolution<T>> for Error {
+    fn from(err: NoSolution<T>) -> Self {
+        Error::new(err.to_string())
+    }
+}
+
+/// The `k` entries whose sum is closest to `target` on the `side` given,
+/// preferring the one below on a tie. `None` if there are fewer than `k`
+/// entries or nothing falls on that side.
+///
+/// Sorts the entries and walks two pointers in from either end for the last
+/// two, under every choice of the first k - 2: O(n log n) for k ≤ 2 and
+/// O(n^(k-1)) above that.
+pub fn nearest_k_sum<T: Expense>(numbers: &[T], k: usize, target: T, side: Nearest) -> Option<Vec<Entry<T>>> {
+    let mut sorted = entries(numbers, &(0..numbers.len()).collect::<Vec<_>>());
+    sorted.sort_by_key(|entry| entry.value);
+
+    let mut best = None;
+    let mut chosen = vec![];
+    nearest_from(&sorted, 0, k, target.wide(), side, &mut chosen, &mut best);
+    best.map(|(_, mut entries)| {
+        entries.sort_by_key(|entry| entry.index);
+        entries
+    })
+}
+
+type Best<T> = Option<(u128, Vec<Entry<T>>)>;
+
+/// Replaces `best` with `chosen` plus `last` if `rank` is ahead of it.
+/// The entries are only copied when they win.
+fn offer<T: Expense>(rank: Option<u128>, chosen: &[Entry<T>], last: &[Entry<T>], best: &mut Best<T>) {
+    if let Some(rank) = rank {
+        if best.as_ref().is_none_or(|(best, _)| rank < *best) {
+            *best = Some((rank, chosen.iter().chain(last).copied().collect()));
+        }
+    }
+}
+
+fn nearest_from<T: Expense>(
+    sorted: &[Entry<T>],
+    start: usize,
+    k: usize,
+    target: i128,
+    side: Nearest,
+    chosen: &mut Vec<Entry<T>>,
+    best: &mut Best<T>,
+) {
+    // Distance of a sum from the target, with sums below it ranking ahead
+    // of sums the same distance above it. `None` if it's on the wrong side.
+    let rank = |off: Option<i128>| match (side, off?) {
+        (Nearest::Below, off) if off > 0 => None,
+        (Nearest::Above, off) if off < 0 => None,
+        (_, off) => Some(off.unsigned_abs().saturating_mul(2).saturating_add(u128::from(off > 0))),
+    };
+
+    match k {
+        0 => offer(rank(0i128.checked_sub(target)), chosen, &[], best),
+        1 => {
+            for entry in sorted[start..].iter() {
+                offer(rank(entry.value.wide().checked_sub(target)), chosen, &[*entry], best);
+            }
+        }
+        2 => {
+            let (mut lo, mut hi) = (start, sorted.len());
+            while lo + 1 < hi {
+                let sum = sorted[lo].value.wide().checked_add(sorted[hi - 1].value.wide());
+                let off = sum.and_then(|sum| sum.checked_sub(target));
+                offer(rank(off), chosen, &[sorted[lo], sorted[hi - 1]], best);
+
+                // An overflowing sum is too far from any i128 target either
+                // way; its sign tells which end to move.
+                let below = match sum {
+                    Some(sum) => sum < target,
+                    None => sorted[lo].value.wide() < 0,
+                };
+                if below {
+                    lo += 1;
+                } else {
+                    hi -= 1;
+                }
+            }
+        }
+        _ => {
+            for (i, entry) in sorted.iter().enumerate().skip(start) {
+                if let Some(rest) = target.checked_sub(entry.value.wide()) {
+                    chosen.push(*entry);
+                    nearest_from(sorted, i + 1, k - 1, rest, side, chosen, best);
+                    chosen.pop();
+                }
+            }
+        }
+    }
+}
+
+/// Two entries at different positions that sum to `target`. Equal values
+/// only pair up when the value appears on two lines.
+pub fn find_pairs<T: Expense>(numbers: &[T], target: T) -> Result<(Entry<T>, Entry<T>), NoSolution<T>> {
+    let mut prev_numbers: HashMap<i128, usize> = HashMap::new();
+    for (index, number) in numbers.iter().enumerate() {
+        if let Some(prev) = target.wide().checked_sub(number.wide()).and_then(|diff| prev_numbers.get(&diff)) {
+            return Ok((Entry { value: numbers[*prev], index: *prev }, Entry { value: *number, index }));
+        }
+        prev_numbers.entry(number.wide()).or_insert(index);
+    }
+    Err(NoSolution::new(2, target))
+}
+
+pub type Triple<T> = (Entry<T>, Entry<T>, Entry<T>);
+
+/// Three entries at different positions that sum to `target`.
+///
+/// Pairs only go into the map once both of their positions are behind the
+/// current one, so the third entry can't be one of the pair's.
+pub fn find_triples<T: Expense>(numbers: &[T], target: T) -> Result<Triple<T>, NoSolution<T>> {
+    let mut pairs: HashMap<i128, (usize, usize)> = HashMap::new();
+
+    for (index3, num3) in numbers.iter().enumerate() {
+        let diff = target.wide().checked_sub(num3.wide());
+        if let Some((index1, index2)) = diff.and_then(|diff| pairs.get(&diff)) {
+            return Ok((
+                Entry { value: numbers[*index1], index: *index1 },
+                Entry { value: numbers[*index2], index: *index2 },
+                Entry { value: *num3, index: index3 },
+            ));
+        }
+
+        for (index1, num1) in numbers[..index3].iter().enumerate() {
+            if let Some(sum) = num1.wide().checked_add(num3.wide()) {
+                pairs.entry(sum).or_insert((index1, index3));
+            }
+        }
+    }
+    Err(NoSolution::new(3, target))
+}
+
+/// Calls `visit` with the positions of every `k` entries of `numbers` that sum
+/// to `target`, in increasing order of position, until it returns
+/// `ControlFlow::Break`. Positions within a combination are always distinct
+/// and increasing, so each combination is visited once. Combinations whose
+/// running sum would overflow `i128` are skipped.
+///
+/// With `n` numbers and `m` matches this takes:
+///
+/// - k = 0: O(1), the empty combination matches a target of 0.
+/// - k = 1: O(n).
+/// - k = 2: O(n + m) expected, one pass with a map of the values seen so far.
+/// - k ≥ 3: O(n^(k-1) + m), every choice of the first k - 2 positions
+///   followed by the k = 2 pass over the rest.
+pub fn visit_k_sum<T, F>(numbers: &[T], k: usize, target: T, mut visit: F)
+where
+    T: Expense,
+    F: FnMut(&[usize]) -> ControlFlow<()>,
+{
+    let mut chosen = Vec::with_capacity(k);
+    let _ = visit_from(numbers, 0, k, target.wide(), &mut chosen, &mut visit);
+}
+
+fn visit_from<T, F>(
+    numbers: &[T],
+    start: usize,
+    k: usize,
+    target: i128,
+    chosen: &mut Vec<usize>,
+    visit: &mut F,
+) -> ControlFlow<()>
+where
+    T: Expense,
+    F: FnMut(&[usize]) -> ControlFlow<()>,
+{
+    match k {
+        0 if target == 0 => visit(chosen),
+        0 => ControlFlow::Continue(()),
+        1 => {
+            for (i, number) in numbers.iter().enumerate().skip(start) {
+                if number.wide() == target {
+                    chosen.push(i);
+                    let flow = visit(chosen);
+                    chosen.pop();
+                    flow?;
+                }
+            }
+            ControlFlow::Continue(())
+        }
+        2 => {
+            let mut seen: HashMap<i128, Vec<usize>> = HashMap::new();
+            for (j, number) in numbers.iter().enumerate().skip(start) {
+                let value = number.wide();
+                if let Some(partners) = target.checked_sub(value).and_then(|diff| seen.get(&diff)) {
+                    for i in partners {
+                        chosen.push(*i);
+                        chosen.push(j);
+                        let flow = visit(chosen);
+                        chosen.truncate(chosen.len() - 2);
+                        flow?;
+                    }
+                }
+                seen.entry(value).or_default().push(j);
+            }
+            ControlFlow::Continue(())
+        }
+        _ => {
+            for (i, number) in numbers.iter().enumerate().skip(start) {
+                if let Some(rest) = target.checked_sub(number.wide()) {
+                    chosen.push(i);
+                    let flow = visit_from(numbers, i + 1, k - 1, rest, chosen, visit);
+                    chosen.pop();
+                    flow?;
+                }
+            }
+            ControlFlow::Continue(())
+        }
+    }
+}
+
+/// Positions of the first `k` entries that sum to `target`.
+pub fn find_k_sum<T: Expense>(numbers: &[T], k: usize, target: T) -> Option<Vec<usize>> {
+    let mut found = None;
+    visit_k_sum(numbers, k, target, |positions| {
+        found = Some(positions.to_vec());
+        ControlFlow::Break(())
+    });
+    found
+}
+
+/// Positions of every combination of `k` entries that sum to `target`.
+pub fn find_all_k_sum<T: Expense>(numbers: &[T], k: usize, target: T) -> Vec<Vec<usize>> {
+    let mut found = vec![];
+    visit_k_sum(numbers, k, target, |positions| {
+        found.push(positions.to_vec());
+        ControlFlow::Continue(())
+    });
+    found
+}
+
+/// Number of combinations of `k` entries that sum to `target`.
+pub fn count_k_sum<T: Expense>(numbers: &[T], k: usize, target: T) -> usize {
+    let mut count = 0;
+    visit_k_sum(numbers, k, target, |_| {
+        count += 1;
+        ControlFlow::Continue(())
+    });
+    count
+}
+
+/// Largest target [`ValueCounts`] is used for. Its table takes a byte for
+/// every value up to the target.
+pub const COUNTING_MAX_TARGET: usize = 1 << 24;
+
+/// How many times each value in `0..=target` appears, capped at three since
+/// no triple needs more, and the distinct values that appear, in order.
+///
+/// A backend for pairs and triples when the entries can't be negative:
+/// anything above the target can't be part of a match and is left out.
+/// Building it is O(n + target), after which a pair takes O(d) and a triple
+/// O(d²) for `d` distinct values, with no hashing.
+#[derive(Debug, Clone)]
+pub struct ValueCounts {
+    counts: Vec<u8>,
+    values: Vec<usize>,
+}
+
+impl ValueCounts {
+    /// `None` when an entry is negative or the target is negative or above
+    /// [`COUNTING_MAX_TARGET`].
+    pub fn new<T: Expense>(numbers: &[T], target: T) -> Option<Self> {
+        let target = usize::try_from(target.wide()).ok().filter(|t| *t <= COUNTING_MAX_TARGET)?;
+
+        let mut counts = vec![0u8; target + 1];
+        for number in numbers {
+            let value = usize::try_from(number.wide()).ok()?;
+            if let Some(count) = counts.get_mut(value) {
+                *count = (*count + 1).min(3);
+            }
+        }
+
+        let values = (0..=target).filter(|value| counts[*value] > 0).collect();
+        Some(ValueCounts { counts, values })
+    }
+
+    fn has(&self, value: usize, times: u8) -> bool {
+        self.counts.get(value).is_some_and(|count| *count >= times)
+    }
+
+    /// Two values, smallest first, that appear often enough to sum to
+    /// `target`.
+    pub fn find_pair(&self, target: usize) -> Option<[usize; 2]> {
+        self.values
+            .iter()
+            .take_while(|a| **a <= target / 2)
+            .find(|a| {
+                let b = target - **a;
+                self.has(b, if b == **a { 2 } else { 1 })
+            })
+            .map(|a| [*a, target - a])
+    }
+
+    /// Three values, smallest first, that appear often enough to sum to
+    /// `target`.
+    pub fn find_triple(&self, target: usize) -> Option<[usize; 3]> {
+        for (i, a) in self.values.iter().enumerate() {
+            if a * 3 > target {
+                break;
+            }
+            for b in self.values[i..].iter() {
+                if a + b * 2 > target {
+                    break;
+                }
+                let c = target - a - b;
+                let needed = |value: usize| [*a, *b, c].iter().filter(|v| **v == value).count() as u8;
+                if self.has(*a, needed(*a)) && self.has(*b, needed(*b)) && self.has(c, needed(c)) {
+                    return Some([*a, *b, c]);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Distinct positions holding `values`, one per value, in increasing order.
+fn positions_of<T: Expense>(numbers: &[T], values: &[usize]) -> Vec<usize> {
+    let mut wanted = values.to_vec();
+    let mut positions = vec![];
+    for (index, number) in numbers.iter().enumerate() {
+        let found = wanted.iter().position(|value| number.wide() == *value as i128);
+        if let Some(found) = found {
+            wanted.swap_remove(found);
+            positions.push(index);
+        }
+    }
+    positions
+}
+
+/// Most table slots per entry [`find_k_sum_auto`] will pay for: past this
+/// the O(target) table costs more than hashing the entries.
+pub const COUNTING_SLOTS_PER_ENTRY: usize = 64;
+
+/// [`find_k_sum`], using [`ValueCounts`] instead for pairs and triples when
+/// the entries and target allow it and the target is at most
+/// [`COUNTING_SLOTS_PER_ENTRY`] times the number of entries, so the table
+/// stays in proportion to the input.
+///
+/// With `d` distinct values, the table finds a pair in O(n + target + d)
+/// and a triple in O(n + target + d²); the triple search is quadratic, not
+/// linear.
+pub fn find_k_sum_auto<T: Expense>(numbers: &[T], k: usize, target: T) -> Option<Vec<usize>> {
+    let worth_it = usize::try_from(target.wide())
+        .is_ok_and(|target| target <= numbers.len().saturating_mul(COUNTING_SLOTS_PER_ENTRY));
+    let counts = match k {
+        2 | 3 if worth_it => ValueCounts::new(numbers, target),
+        _ => None,
+    };
+    let counts = match counts {
+        Some(counts) => counts,
+        None => return find_k_sum(numbers, k, target),
+    };
+
+    let target = target.wide() as usize;
+    let values = match k {
+        2 => counts.find_pair(target).map(|values| values.to_vec()),
+        _ => counts.find_triple(target).map(|values| values.to_vec()),
+    };
+    values.map(|values| positions_of(numbers, &values))
+}
+
+/// Product of the first `k` entries that sum to 2020, the answer to both
+/// parts. When there are none and `nearest` is given, the error names the
+/// closest combination on that side, which costs as much as
+/// [`nearest_k_sum`].
+pub fn solve_k_sum<T: Expense>(numbers: &[T], k: usize, nearest: Option<Nearest>) -> Result<Answer, Error> {
+    let target = T::from(2020);
+    let positions = find_k_sum_auto(numbers, k, target).ok_or_else(|| {
+        let err = NoSolution::new(k, target);
+        match nearest {
+            Some(side) => err.with_nearest(numbers, side),
+            None => err,
+        }
+    })?;
+    product(&positions.iter().map(|i| numbers[*i]).collect::<Vec<_>>())
+}
+
+/// Subsets of entries that sum to a target: how many there are, and one
+/// with as few entries as possible. `count` saturates at `u128::MAX`.
+#[derive(Debug, Clone)]
+pub struct SubsetSum<T> {
+    pub count: u128,
+    pub smallest: Option<Vec<Entry<T>>>,
+}
+
+/// The subset search needed more than its budget of steps.
+#[derive(Debug, Clone)]
+pub struct BudgetExceeded {
+    pub budget: usize,
+    pub entries_done: usize,
+}
+
+impl Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Gave up after {} steps, {} entries in", self.budget, self.entries_done)
+    }
+}
+
+impl std::error::Error for BudgetExceeded {}
+
+impl From<BudgetExceeded> for Error {
+    fn from(err: BudgetExceeded) -> Self {
+        Error::new(err.to_string())
+    }
+}
+
+/// Search budget used when none is given.
+pub const DEFAULT_SUBSET_BUDGET: usize = 50_000_000;
+
+/// For one reachable sum: how many subsets reach it, and the smallest one as
+/// a node in the backpointer arena (`None` only while starting from the
+/// empty subset).
+#[derive(Debug, Copy, Clone)]
+struct Reach {
+    count: u128,
+    size: usize,
+    node: Option<usize>,
+}
+
+/// Counts the non-empty subsets of entries, of any size, that sum to
+/// `target` and finds one of the smallest. The empty subset is never a
+/// match, even for a target of 0.
+///
+/// Works through the entries one at a time, keeping every sum reachable so
+/// far. The smallest subset for each sum is a chain of backpointers into an
+/// arena, so extending one doesn't copy it. When every entry is
+/// non-negative, sums past the target are dropped. Each reachable sum looked
+/// at for an entry costs a step, and the search stops with
+/// [`BudgetExceeded`] once it has taken `budget` steps. Sums that would
+/// overflow `i128` are dropped.
+pub fn subset_sum<T: Expense>(numbers: &[T], target: T, budget: usize) -> Result<SubsetSum<T>, BudgetExceeded> {
+    let target = target.wide();
+    let prune = numbers.iter().all(|n| n.wide() >= 0);
+
+    // (entry index, previous node)
+    let mut arena: Vec<(usize, Option<usize>)> = vec![];
+    // Only non-empty subsets go in; each entry also starts one of its own.
+    let mut reach: HashMap<i128, Reach> = HashMap::new();
+    let empty = Reach { count: 1, size: 0, node: None };
+
+    let mut steps = 0;
+    for (index, number) in numbers.iter().enumerate() {
+        steps += reach.len() + 1;
+        if steps > budget {
+            return Err(BudgetExceeded { budget, entries_done: index });
+        }
+
+        let value = number.wide();
+        let extended = reach
+            .iter()
+            .chain(iter::once((&0, &empty)))
+            .filter_map(|(sum, from)| Some((sum.checked_add(value)?, *from)))
+            .filter(|(sum, _)| !prune || *sum <= target)
+            .collect::<Vec<_>>();
+
+        for (sum, from) in extended {
+            let size = from.size + 1;
+            let to = reach.entry(sum).or_insert(Reach { count: 0, size: usize::MAX, node: None });
+            to.count = to.count.saturating_add(from.count);
+            if size < to.size {
+                arena.push((index, from.node));
+                to.size = size;
+                to.node = Some(arena.len() - 1);
+            }
+        }
+    }
+
+    let found = match reach.get(&target) {
+        Some(found) => *found,
+        None => return Ok(SubsetSum { count: 0, smallest: None }),
+    };
+
+    let mut positions = vec![];
+    let mut node = found.node;
+    while let Some(at) = node {
+        positions.push(arena[at].0);
+        node = arena[at].1;
+    }
+    positions.reverse();
+
+    Ok(SubsetSum { count: found.count, smallest: Some(entries(numbers, &positions)) })
+}
+
+fn part1_find_pairs(lines: &[String]) -> error::Result<Answer> {
+    let (a, b) = find_pairs(&parse_numbers::<i128>(lines)?, 2020)?;
+    product(&[a.value, b.value])
+}
+
+fn part2_find_triples(lines: &[String]) -> error::Result<Answer> {
+    let (a, b, c) = find_triples(&parse_numbers::<i128>(lines)?, 2020)?;
+    product(&[a.value, b.value, c.value])
+}
+
+/// Checks every pair of entries, O(n²). Slow, but hard to get wrong.
+pub fn find_pairs_scan<T: Expense>(numbers: &[T], target: T) -> Option<(T, T)> {
+    for (i, a) in numbers.iter().enumerate() {
+        for b in numbers[i + 1..].iter() {
+            if a.wide().checked_add(b.wide()) == Some(target.wide()) {
+                return Some((*a, *b));
+            }
+        }
+    }
+    None
+}
+
+/// Checks every triple of distinct entries, O(n³).
+pub fn find_triples_scan<T: Expense>(numbers: &[T], target: T) -> Option<(T, T, T)> {
+    for (i, a) in numbers.iter().enumerate() {
+        for (j, b) in numbers.iter().enumerate().skip(i + 1) {
+            for c in numbers[j + 1..].iter() {
+                let sum = a.wide().checked_add(b.wide()).and_then(|sum| sum.checked_add(c.wide()));
+                if sum == Some(target.wide()) {
+                    return Some((*a, *b, *c));
+                }
+            }
+        }
+    }
+    None
+}
+
+fn hash_k_sum(lines: &[String], k: usize) -> error::Result<Answer> {
+    let numbers = parse_numbers::<i128>(lines)?;
+    let positions = find_k_sum(&numbers, k, 2020).ok_or_else(|| NoSolution::new(k, 2020))?;
+    product(&positions.iter().map(|i| numbers[*i]).collect::<Vec<_>>())
+}
+
+fn part1_hash(lines: &[String]) -> error::Result<Answer> {
+    hash_k_sum(lines, 2)
+}
+
+fn part2_hash(lines: &[String]) -> error::Result<Answer> {
+    hash_k_sum(lines, 3)
+}
+
+fn part1_scan(lines: &[String]) -> error::Result<Answer> {
+    let (a, b) = find_pairs_scan(&parse_numbers::<i128>(lines)?, 2020).ok_or("No pair sums to 2020")?;
+    product(&[a, b])
+}
+
+fn part2_scan(lines: &[String]) -> error::Result<Answer> {
+    let (a, b, c) = find_triples_scan(&parse_numbers::<i128>(lines)?, 2020).ok_or("No triple sums to 2020")?;
+    product(&[a, b, c])
+}
+
+/// An expense report of `size` entries (at least five) in which exactly one
+/// pair and one triple of entries sum to 2020.
+///
+/// The planted pair and triple use numbers below 1010, except the pair's
+/// partner; every other entry is above 1010 and chosen so it can't complete a
+/// pair or triple with the planted numbers.
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let (pair, triple) = loop {
+        let a = rng.between(1, 1009) as i32;
+        let x = rng.between(10, 1009) as i32;
+        let y = rng.between(10, 1009) as i32;
+        let z = 2020 - x - y;
+        let small = [a, x, y, z];
+        let distinct = (0..4).all(|i| (i + 1..4).all(|j| small[i] != small[j]));
+        if !(1..1010).contains(&z) || !distinct {
+            continue;
+        }
+
+        let planted = [a, 2020 - a, x, y, z];
+        let pairs = (0..5)
+            .flat_map(|i| (i + 1..5).map(move |j| (i, j)))
+            .filter(|&(i, j)| planted[i] + planted[j] == 2020)
+            .count();
+        let triples = (0..5)
+            .flat_map(|i| (i + 1..5).flat_map(move |j| (j + 1..5).map(move |k| (i, j, k))))
+            .filter(|&(i, j, k)| planted[i] + planted[j] + planted[k] == 2020)
+            .count();
+        if pairs == 1 && triples == 1 {
+            break ((a, 2020 - a), (x, y, z));
+        }
+    };
+
+    let small = [pair.0, triple.0, triple.1, triple.2];
+    let forbidden = |n: i32| {
+        n == pair.1
+            || small.iter().any(|s| n + s == 2020)
+            || small.iter().enumerate().any(|(i, s)| small[i + 1..].iter().any(|t| n + s + t == 2020))
+    };
+
+    let mut numbers = vec![pair.0, pair.1, triple.0, triple.1, triple.2];
+    while numbers.len() < size {
+        let n = rng.between(1011, 2019) as i32;
+        if !forbidden(n) {
+            numbers.push(n);
+        }
+    }
+    rng.shuffle(&mut numbers);
+
+    Generated::new(
+        numbers.iter().map(|n| n.to_string()).collect(),
+        Some((pair.0 * pair.1).into()),
+        Some((triple.0 * triple.1 * triple.2).into()),
+    )
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u32 = 1;
+    type Parsed = Expenses;
+
+    const IMPLEMENTATIONS: &'static [Implementation] = &[
+        Implementation { name: "pair-scan", part: 1, solve: part1_scan },
+        Implementation { name: "find-pairs", part: 1, solve: part1_find_pairs },
+        Implementation { name: "hash-k-sum", part: 1, solve: part1_hash },
+        Implementation { name: "triple-scan", part: 2, solve: part2_scan },
+        Implementation { name: "find-triples", part: 2, solve: part2_find_triples },
+        Implementation { name: "hash-k-sum", part: 2, solve: part2_hash },
+    ];
+
+    fn parse(lines: &[String]) -> error::Result<Expenses> {
+        Expenses::parse(lines)
+    }
+
+    fn part1(expenses: &Expenses) -> error::Result<Answer> {
+        expenses.solve_k_sum(2, None)
+    }
+
+    fn part2(expenses: &Expenses) -> error::Result<Answer> {
+        expenses.solve_k_sum(3, None)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values<T: Copy>(found: &[Entry<T>]) -> Vec<T> {
+        found.iter().map(|entry| entry.value).collect()
+    }
+
+    fn lines(text: &[&str]) -> Vec<String> {
+        text.iter().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn expenses_pick_the_narrowest_width() {
+        let width = |text: &[&str]| Expenses::parse(&lines(text)).unwrap().width();
+        assert_eq!(width(&["1721", "-2147483648", "2147483647"]), "i32");
+        assert_eq!(width(&["1721", "2147483648"]), "i64");
+        assert_eq!(width(&["-9223372036854775809", "1"]), "i128");
+        assert_eq!(width(&[]), "i32");
+    }
+
+    #[test]
+    fn expenses_too_wide_for_i128_are_rejected() {
+        let err = Expenses::parse(&lines(&["1", "170141183460469231731687303715884105728"])).unwrap_err();
+        assert_eq!(err.message(), "Number doesn't fit in i128: 170141183460469231731687303715884105728");
+        assert_eq!(err.line(), Some(2));
+
+        let err = Expenses::parse(&lines(&["12a"])).unwrap_err();
+        assert_eq!(err.message(), "Invalid number: 12a");
+    }
+
+    #[test]
+    fn product_reports_overflow() {
+        assert_eq!(product(&[i64::MAX, 2]).unwrap(), Answer::from(i64::MAX as i128 * 2));
+        let err = product(&[i128::MAX, 2]).unwrap_err();
+        assert_eq!(err.message(), format!("Product of {} * 2 overflows i128", i128::MAX));
+    }
+
+    #[test]
+    fn pairs_do_not_reuse_an_entry() {
+        assert!(find_pairs(&[1010, 3], 2020).is_err());
+        assert!(find_pairs(&[3, 1010], 2020).is_err());
+    }
+
+    #[test]
+    fn equal_values_on_two_lines_pair_up() {
+        let (a, b) = find_pairs(&[7, 1010, 5, 1010], 2020).unwrap();
+        assert_eq!((a.index, b.index), (1, 3));
+        assert_eq!(values(&[a, b]), vec![1010, 1010]);
+    }
+
+    #[test]
+    fn pairs_find_the_puzzle_example() {
+        let (a, b) = find_pairs(&[1721, 979, 366, 299, 675, 1456], 2020).unwrap();
+        assert_eq!(values(&[a, b]), vec![1721, 299]);
+    }
+
+    #[test]
+    fn triples_do_not_reuse_an_entry() {
+        assert!(find_triples(&[1010, 0, 5], 2020).is_err());
+        assert!(find_triples(&[1010, 5], 2020).is_err());
+        assert!(find_triples(&[0, 2020], 2020).is_err());
+    }
+
+    #[test]
+    fn triples_use_three_distinct_lines() {
+        let (a, b, c) = find_triples(&[1010, 0, 1010, 5], 2020).unwrap();
+        let mut found = values(&[a, b, c]);
+        found.sort();
+        assert_eq!(found, vec![0, 1010, 1010]);
+        let mut lines = vec![a.index, b.index, c.index];
+        lines.sort();
+        lines.dedup();
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn triples_find_the_puzzle_example() {
+        let (a, b, c) = find_triples(&[1721, 979, 366, 299, 675, 1456], 2020).unwrap();
+        let mut found = values(&[a, b, c]);
+        found.sort();
+        assert_eq!(found, vec![366, 675, 979]);
+    }
+
+    #[test]
+    fn nearest_k_sum_keeps_to_its_side() {
+        let numbers = [1000, 1017, 1021, 985];
+        assert_eq!(values(&nearest_k_sum(&numbers, 2, 2020, Nearest::Either).unwrap()), vec![1000, 1021]);
+        assert_eq!(values(&nearest_k_sum(&numbers, 2, 2020, Nearest::Below).unwrap()), vec![1000, 1017]);
+        assert_eq!(values(&nearest_k_sum(&numbers, 2, 2020, Nearest::Above).unwrap()), vec![1000, 1021]);
+        assert_eq!(values(&nearest_k_sum(&numbers, 3, 3000, Nearest::Either).unwrap()), vec![1000, 1017, 985]);
+        assert!(nearest_k_sum(&numbers, 2, 100, Nearest::Below).is_none());
+    }
+
+    #[test]
+    fn k_sum_of_no_entries_is_zero() {
+        assert_eq!(find_all_k_sum(&[1, 2], 0, 0), vec![Vec::<usize>::new()]);
+        assert_eq!(count_k_sum(&[1, 2], 0, 3), 0);
+        assert_eq!(count_k_sum::<i32>(&[], 0, 0), 1);
+    }
+
+    #[test]
+    fn k_sum_finds_single_entries() {
+        assert_eq!(find_all_k_sum(&[4, 7, 4], 1, 4), vec![vec![0], vec![2]]);
+        assert_eq!(find_k_sum(&[4, 7, 4], 1, 5), None);
+    }
+
+    #[test]
+    fn k_sum_finds_pairs_in_order() {
+        assert_eq!(find_all_k_sum(&[1, 4, 3, 2], 2, 5), vec![vec![0, 1], vec![2, 3]]);
+        assert_eq!(find_k_sum(&[1010, 3], 2, 2020), None);
+    }
+
+    #[test]
+    fn k_sum_finds_larger_combinations() {
+        assert_eq!(find_all_k_sum(&[1, 2, 3, 4], 3, 6), vec![vec![0, 1, 2]]);
+        assert_eq!(find_all_k_sum(&[1, 2, 3, 4, 0], 4, 10), vec![vec![0, 1, 2, 3]]);
+        assert_eq!(count_k_sum(&[1, 2, 3, 4, 5], 3, 9), 2);
+        assert_eq!(count_k_sum(&[1, 2], 3, 3), 0);
+    }
+
+    #[test]
+    fn k_sum_counts_every_line_of_a_duplicate() {
+        assert_eq!(count_k_sum(&[1, 1, 1, 1], 2, 2), 6);
+        assert_eq!(count_k_sum(&[1, 1, 1, 1], 3, 3), 4);
+        assert_eq!(count_k_sum(&[1, 1, 1, 1], 1, 1), 4);
+    }
+
+    #[test]
+    fn k_sum_stops_on_break() {
+        for k in [1, 2, 3] {
+            let mut visited = 0;
+            visit_k_sum(&[1, 1, 1, 1, 1], k, k as i32, |_| {
+                visited += 1;
+                if visited == 2 {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            });
+            assert_eq!(visited, 2, "k = {}", k);
+        }
+    }
+
+    #[test]
+    fn subset_sum_skips_the_empty_subset() {
+        let found = subset_sum(&[0, 0, 5], 0, DEFAULT_SUBSET_BUDGET).unwrap();
+        assert_eq!(found.count, 3);
+        assert_eq!(found.smallest.map(|entries| entries.len()), Some(1));
+
+        let found = subset_sum(&[3, 4], 0, DEFAULT_SUBSET_BUDGET).unwrap();
+        assert_eq!(found.count, 0);
+        assert!(found.smallest.is_none());
+    }
+
+    #[test]
+    fn subset_sum_counts_and_finds_the_smallest() {
+        let found = subset_sum(&[1, 2, 3, 4, 5], 5, DEFAULT_SUBSET_BUDGET).unwrap();
+        assert_eq!(found.count, 3);
+        assert_eq!(values(&found.smallest.unwrap()), vec![5]);
+    }
+
+    #[test]
+    fn large_reports_without_a_match_fail_fast() {
+        // Any two of these already sum past 2020.
+        let numbers = (0..1_000_000).map(|i| 1011 + i % 1010).collect::<Vec<i32>>();
+        for k in [2, 3] {
+            let err = solve_k_sum(&numbers, k, None).unwrap_err();
+            assert_eq!(err.message(), format!("No {} entries sum to 2020", k));
+        }
+    }
+
+    #[test]
+    fn auto_k_sum_matches_hashing() {
+        let numbers = [1721, 979, 366, 299, 675, 1456];
+        assert_eq!(find_k_sum_auto(&numbers, 2, 2020), Some(vec![0, 3]));
+        let mut triple = find_k_sum_auto(&numbers, 3, 2020).unwrap();
+        triple.sort();
+        assert_eq!(triple, vec![1, 2, 4]);
+
+        // Too few entries for the table to pay off: hashing finds the same.
+        assert_eq!(find_k_sum_auto(&[8_000_000, 8_000_000], 2, 16_000_000), Some(vec![0, 1]));
+    }
+}
@@ -0,0 +1,1176 @@
+use std::convert::TryFrom;
+use std::fmt::{self, Display};
+use std::io::{self, BufRead, Write};
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, SyncSender};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::diff::panic_message;
+use crate::error::{self, span_of, Error};
+use crate::generate::Generated;
+use crate::json;
+use crate::rng::Rng;
+use crate::solution::{Answer, Solution};
+
+
+/// The numbers of a policy: a range `1-3`, an open range `2-` with no upper
+/// end, or a single number `3`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Bounds {
+    Range(i32, i32),
+    AtLeast(i32),
+    Exactly(i32),
+}
+
+impl Bounds {
+    pub fn low(&self) -> i32 {
+        match *self {
+            Bounds::Range(low, _) | Bounds::AtLeast(low) | Bounds::Exactly(low) => low,
+        }
+    }
+
+    /// The upper end, or `None` for an open range.
+    pub fn high(&self) -> Option<i32> {
+        match *self {
+            Bounds::Range(_, high) | Bounds::Exactly(high) => Some(high),
+            Bounds::AtLeast(_) => None,
+        }
+    }
+
+    pub fn contains(&self, n: i32) -> bool {
+        n >= self.low() && self.high().is_none_or(|high| n <= high)
+    }
+}
+
+impl Display for Bounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bounds::Range(low, high) => write!(f, "{}-{}", low, high),
+            Bounds::AtLeast(low) => write!(f, "{}-", low),
+            Bounds::Exactly(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+/// Where a line's numbers are in its text, as character columns. For an
+/// open range `high` is the dash, and for a single number both are the
+/// number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BoundSpans {
+    pub low: Range<usize>,
+    pub high: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedLine {
+    /// Set by a leading `!`: the policy's verdict is flipped.
+    pub negated: bool,
+    pub bounds: Bounds,
+    pub letters: Vec<char>,
+    pub password: String,
+    pub spans: BoundSpans,
+}
+
+impl Display for ParsedLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ParsedLine({}, {}, {})", self.rule(), self.letter_list(), self.password)
+    }
+}
+
+impl ParsedLine {
+    /// The policy part of the line, `!1-3` or `2-`.
+    pub fn rule(&self) -> String {
+        format!("{}{}", if self.negated { "!" } else { "" }, self.bounds)
+    }
+
+    /// The letters as written in the line, `a,b`.
+    pub fn letter_list(&self) -> String {
+        self.letters.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(",")
+    }
+
+    /// Whether `c` is one of the policy's letters.
+    pub fn has_letter(&self, c: char) -> bool {
+        self.letters.contains(&c)
+    }
+
+    /// The line as it would appear in an input, `1-3 a: abcde`.
+    pub fn to_line(&self) -> String {
+        format!("{} {}: {}", self.rule(), self.letter_list(), self.password)
+    }
+}
+
+/// Which of a line's two numbers something refers to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Field {
+    Low,
+    High,
+}
+
+/// Why a policy couldn't be checked against a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PolicyError {
+    /// A position below 1; positions count from 1.
+    PositionBeforeStart { field: Field, position: i32 },
+    /// A position past the last character of the password.
+    PositionPastEnd { field: Field, position: i32, len: usize },
+    /// An open range like `2-` where a second position is needed.
+    OpenRange { low: i32 },
+}
+
+impl PolicyError {
+    pub fn field(&self) -> Field {
+        match self {
+            PolicyError::PositionBeforeStart { field, .. } => *field,
+            PolicyError::PositionPastEnd { field, .. } => *field,
+            PolicyError::OpenRange { .. } => Field::High,
+        }
+    }
+
+    /// Turns this into an error pointing at the number it's about in `text`,
+    /// line `index` of the input that `line` was parsed from. For an open
+    /// range that's the dash.
+    pub fn at_line(&self, index: usize, line: &ParsedLine, text: &str) -> Error {
+        self.on_line(index, line).with_line(index, text)
+    }
+
+    /// [`PolicyError::at_line`] without the text of the line, for a caller
+    /// that still has the input to quote it with [`Error::quoting`].
+    pub fn on_line(&self, index: usize, line: &ParsedLine) -> Error {
+        let span = match self.field() {
+            Field::Low => line.spans.low.clone(),
+            Field::High => line.spans.high.clone(),
+        };
+        Error::new(self.to_string()).on_line(index).with_span(span)
+    }
+}
+
+impl Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyError::PositionBeforeStart { position, .. } => {
+                write!(f, "Position {} is out of range, positions start at 1", position)
+            }
+            PolicyError::PositionPastEnd { position, len, .. } => write!(
+                f,
+                "Position {} is past the end of the password, which has {} characters",
+                position, len
+            ),
+            PolicyError::OpenRange { low } => {
+                write!(f, "Open range {}- has no second position", low)
+            }
+        }
+    }
+}
+
+impl std::error::Error for PolicyError {}
+
+/*
+impl<T> Display for Vec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[");
+        for item in self.iter() {
+            write!(f, "{}", item);
+        }
+        write!(f, "]");
+        Ok(())
+    }
+}
+*/
+
+/// A rule for reading a password line's policy and checking its password.
+pub trait PasswordPolicy: Sync {
+    /// Name the policy is picked by, e.g. `toboggan`.
+    fn name(&self) -> &str;
+
+    /// Whether the line's password follows its policy, or why that can't
+    /// be told.
+    fn check(&self, line: &ParsedLine) -> Result<bool, PolicyError>;
+
+    /// Why the line passes or fails, for audit reports.
+    fn explain(&self, line: &ParsedLine) -> Result<String, PolicyError> {
+        let verdict = if self.check(line)? { "passes" } else { "fails" };
+        Ok(format!("{} {}", verdict, self.name()))
+    }
+}
+
+/// The letters of a line for explanations, `letter 'a'` or `letters 'a', 'b'`.
+fn letters_named(line: &ParsedLine) -> String {
+    let quoted = line.letters.iter().map(|c| format!("'{}'", c)).collect::<Vec<_>>();
+    let noun = if quoted.len() == 1 { "letter" } else { "letters" };
+    format!("{} {}", noun, quoted.join(", "))
+}
+
+/// The sled rental shop's policy: the letters appear between `low` and
+/// `high` times between them, at least `low` times for an open range, or
+/// exactly the given number of times.
+pub struct SledRental;
+
+impl PasswordPolicy for SledRental {
+    fn name(&self) -> &str {
+        "sled-rental"
+    }
+
+    fn check(&self, line: &ParsedLine) -> Result<bool, PolicyError> {
+        let count: i32 = line.password.chars().fold(0, |count, c| {
+            count + if line.has_letter(c) { 1 } else { 0 }
+        });
+
+        Ok(line.bounds.contains(count) != line.negated)
+    }
+
+    fn explain(&self, line: &ParsedLine) -> Result<String, PolicyError> {
+        let count = line.password.chars().filter(|c| line.has_letter(*c)).count();
+        let appear = if line.letters.len() == 1 { "appears" } else { "appear" };
+        let times = if count == 1 { "time" } else { "times" };
+        let required = match line.bounds {
+            Bounds::Range(low, high) => format!("{}-{}", low, high),
+            Bounds::AtLeast(low) => format!("at least {}", low),
+            Bounds::Exactly(n) => format!("exactly {}", n),
+        };
+        let not = if line.negated { "not " } else { "" };
+        Ok(format!(
+            "{} {} {} {}, required {}{}",
+            letters_named(line), appear, count, times, not, required
+        ))
+    }
+}
+
+/// The toboggan company's policy: a letter is at exactly one of the
+/// 1-based positions `low` and `high`. A single number is the one position
+/// a letter has to be at. Open ranges don't name positions.
+pub struct Toboggan;
+
+impl Toboggan {
+    /// The positions the line names, each with the character found there.
+    fn lookup(line: &ParsedLine) -> Result<Vec<(i32, char)>, PolicyError> {
+        let positions = match line.bounds {
+            Bounds::Range(low, high) => vec![(low, Field::Low), (high, Field::High)],
+            Bounds::Exactly(position) => vec![(position, Field::Low)],
+            Bounds::AtLeast(low) => return Err(PolicyError::OpenRange { low }),
+        };
+
+        let password = line.password.chars().collect::<Vec<char>>();
+        positions
+            .into_iter()
+            .map(|(position, field)| Ok((position, char_at(&password, position, field)?)))
+            .collect()
+    }
+}
+
+impl PasswordPolicy for Toboggan {
+    fn name(&self) -> &str {
+        "toboggan"
+    }
+
+    fn check(&self, line: &ParsedLine) -> Result<bool, PolicyError> {
+        let found = Self::lookup(line)?;
+
+        let matches = found.iter().filter(|(_, c)| line.has_letter(*c)).count();
+        Ok((matches == 1) != line.negated)
+    }
+
+    fn explain(&self, line: &ParsedLine) -> Result<String, PolicyError> {
+        let found = Self::lookup(line)?;
+        let at = found.iter().map(|(_, c)| line.has_letter(*c)).collect::<Vec<_>>();
+        let is = if line.letters.len() == 1 { "is" } else { "are" };
+        let letters = letters_named(line);
+
+        let seen = match at.as_slice() {
+            [true, true] => format!("{} {} at both positions {} and {}", letters, is, found[0].0, found[1].0),
+            [false, false] => format!(
+                "{} {} at neither position {} ('{}') nor {} ('{}')",
+                letters, is, found[0].0, found[0].1, found[1].0, found[1].1
+            ),
+            [true, false] => format!("{} {} at position {} only", letters, is, found[0].0),
+            [false, true] => format!("{} {} at position {} only", letters, is, found[1].0),
+            [true] => format!("{} {} at position {}", letters, is, found[0].0),
+            _ => format!("{} {} not at position {} ('{}')", letters, is, found[0].0, found[0].1),
+        };
+
+        if self.check(line)? {
+            return Ok(seen);
+        }
+        let required = match (found.len(), line.negated) {
+            (1, false) => "required to be",
+            (1, true) => "required not to be",
+            (_, false) => "required at exactly one",
+            (_, true) => "required at neither or both",
+        };
+        Ok(format!("{}, {}", seen, required))
+    }
+}
+
+/// The character at 1-based `position`.
+pub fn char_at(password: &[char], position: i32, field: Field) -> Result<char, PolicyError> {
+    let index = usize::try_from(position)
+        .ok()
+        .and_then(|position| position.checked_sub(1))
+        .ok_or(PolicyError::PositionBeforeStart { field, position })?;
+
+    password
+        .get(index)
+        .copied()
+        .ok_or(PolicyError::PositionPastEnd { field, position, len: password.len() })
+}
+
+/// A policy made from a name and a function, for one-off rules.
+pub struct FnPolicy {
+    name: String,
+    check: fn(&ParsedLine) -> Result<bool, PolicyError>,
+}
+
+impl FnPolicy {
+    pub fn new<N: Into<String>>(name: N, check: fn(&ParsedLine) -> Result<bool, PolicyError>) -> Self {
+        FnPolicy { name: name.into(), check }
+    }
+}
+
+impl PasswordPolicy for FnPolicy {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn check(&self, line: &ParsedLine) -> Result<bool, PolicyError> {
+        (self.check)(line)
+    }
+}
+
+/// Policies that can be picked by name. Starts out with the built-in ones.
+pub struct Policies {
+    policies: Vec<Box<dyn PasswordPolicy>>,
+}
+
+impl Default for Policies {
+    fn default() -> Self {
+        Policies { policies: vec![Box::new(SledRental), Box::new(Toboggan)] }
+    }
+}
+
+impl Policies {
+    /// Adds `policy`, unless one with its name is already registered.
+    pub fn register(&mut self, policy: Box<dyn PasswordPolicy>) -> Result<(), Error> {
+        if self.get(policy.name()).is_some() {
+            return Err(Error::new(format!("A password policy named {} is already registered", policy.name())));
+        }
+        self.policies.push(policy);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn PasswordPolicy> {
+        self.policies.iter().find(|policy| policy.name() == name).map(|policy| policy.as_ref())
+    }
+
+    /// Like [`Policies::get`], with an error listing the known names.
+    pub fn find(&self, name: &str) -> Result<&dyn PasswordPolicy, Error> {
+        self.get(name).ok_or_else(|| {
+            Error::new(format!("Unknown password policy: {} (known: {})", name, self.names().join(", ")))
+        })
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.policies.iter().map(|policy| policy.name()).collect()
+    }
+}
+
+/// Number of lines `policy` accepts. Fails on the first line it can't be
+/// checked against, pointing at the line by number; the caller quotes it.
+pub fn count_valid(policy: &dyn PasswordPolicy, lines: &[ParsedLine]) -> Result<usize, Error> {
+    let mut count = 0;
+    for (index, line) in lines.iter().enumerate() {
+        if policy.check(line).map_err(|err| err.on_line(index, line))? {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Lines handed to a [`validate_stream`] worker at a time.
+pub const STREAM_CHUNK: usize = 4096;
+
+/// What [`validate_stream`] found: the number of lines read, and how many of
+/// them each policy accepts, in the order the policies were given.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamTotals {
+    pub lines: usize,
+    pub valid: Vec<usize>,
+}
+
+/// Checks a chunk of lines starting at line `start`, adding to `valid`. A
+/// policy that panics fails the line, so its worker carries on and the
+/// reader is never left waiting on a dead pool.
+fn check_chunk(policies: &[&dyn PasswordPolicy], start: usize, lines: &[String], valid: &mut [usize]) -> Result<(), Error> {
+    for (index, text) in (start..).zip(lines) {
+        let line = parse_line(text).map_err(|err| err.with_line(index, text))?;
+        for (policy, count) in policies.iter().zip(valid.iter_mut()) {
+            let checked = panic::catch_unwind(AssertUnwindSafe(|| policy.check(&line))).map_err(|payload| {
+                let message = format!("Policy {} panicked: {}", policy.name(), panic_message(payload.as_ref()));
+                Error::at_line(message, index, text)
+            })?;
+            if checked.map_err(|err| err.at_line(index, &line, text))? {
+                *count += 1;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whichever of two failures is on the earlier line.
+fn earliest(current: Option<Error>, found: Error) -> Option<Error> {
+    match current {
+        Some(current) if current.line() <= found.line() => Some(current),
+        _ => Some(found),
+    }
+}
+
+/// Reads `reader` in chunks of `chunk` lines and sends each with the index
+/// of its first line, until the input ends or a worker reports a failure.
+/// Returns the number of lines sent.
+fn read_chunks<R: BufRead>(
+    reader: R,
+    chunk: usize,
+    sender: SyncSender<(usize, Vec<String>)>,
+    failed_at: &AtomicUsize,
+) -> Result<usize, Error> {
+    let mut lines = reader.lines();
+    let mut read = 0;
+    while failed_at.load(Ordering::Relaxed) == usize::MAX {
+        let mut batch = Vec::with_capacity(chunk);
+        for line in lines.by_ref().take(chunk) {
+            let line = line.map_err(|err| {
+                Error::new(format!("Failed to read line {}", read + batch.len() + 1)).caused_by(err)
+            })?;
+            batch.push(line);
+        }
+        if batch.is_empty() {
+            break;
+        }
+
+        let start = read;
+        read += batch.len();
+        if sender.send((start, batch)).is_err() {
+            break;
+        }
+    }
+    Ok(read)
+}
+
+/// Checks every line of `reader` against each of `policies` without holding
+/// the whole input. Lines are read in chunks of `chunk` and parsed once each
+/// by `workers` threads, with at most two chunks per worker waiting. Fails
+/// with the first line, in input order, that doesn't parse or can't be
+/// checked.
+pub fn validate_stream<R: BufRead>(
+    reader: R,
+    policies: &[&dyn PasswordPolicy],
+    workers: usize,
+    chunk: usize,
+) -> Result<StreamTotals, Error> {
+    let workers = workers.max(1);
+    let chunk = chunk.max(1);
+    let (sender, receiver) = mpsc::sync_channel::<(usize, Vec<String>)>(workers * 2);
+    let receiver = Mutex::new(receiver);
+    // Start of the earliest chunk that failed. Chunks after it are skipped,
+    // ones before it are still checked in case they fail on an earlier line.
+    let failed_at = AtomicUsize::new(usize::MAX);
+
+    thread::scope(|scope| {
+        let handles = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut valid = vec![0; policies.len()];
+                    let mut failure = None;
+                    loop {
+                        let received = receiver.lock().unwrap_or_else(|err| err.into_inner()).recv();
+                        let (start, lines) = match received {
+                            Ok(chunk) => chunk,
+                            Err(_) => break,
+                        };
+                        if start > failed_at.load(Ordering::Relaxed) {
+                            continue;
+                        }
+                        if let Err(err) = check_chunk(policies, start, &lines, &mut valid) {
+                            failed_at.fetch_min(start, Ordering::Relaxed);
+                            failure = earliest(failure, err);
+                        }
+                    }
+                    (valid, failure)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let read = read_chunks(reader, chunk, sender, &failed_at);
+
+        let mut valid = vec![0; policies.len()];
+        let mut failure = None;
+        for handle in handles {
+            let (counts, found) = handle.join().map_err(|_| Error::new("A validation worker panicked"))?;
+            for (total, count) in valid.iter_mut().zip(counts) {
+                *total += count;
+            }
+            if let Some(err) = found {
+                failure = earliest(failure, err);
+            }
+        }
+
+        if let Some(err) = failure {
+            return Err(err);
+        }
+        Ok(StreamTotals { lines: read?, valid })
+    })
+}
+
+/*
+fn invalid_line(line: &String) -> String {
+    format!("Invalid line: '{}'", line)
+}
+*/
+
+pub fn parse_line(line: &str) -> Result<ParsedLine, Error> {
+    let expected = "Expected `[!]<min>[-[<max>]] <letter>[,<letter>...]: <password>`";
+    let err = |part: &str, message: &str| Error::new(message).with_span(span_of(line, part));
+    let number = |part: &str, message: &str| {
+        let n = part.parse::<i32>().map_err(|e| err(part, message).caused_by(e))?;
+        if n < 0 {
+            return Err(err(part, "Numbers in a policy can't be negative"));
+        }
+        Ok(n)
+    };
+
+    let (left, password) = line.split_once(": ").ok_or_else(|| err(line, expected))?;
+    let password = password.to_string();
+
+    let (rule, list) = left.split_once(' ').ok_or_else(|| err(left, expected))?;
+    if list.is_empty() {
+        return Err(err(list, "Missing the letter, or letters like `a,b`, before the `:`"));
+    }
+    let mut letters = vec![];
+    for item in list.split(',') {
+        if item.is_empty() {
+            return Err(err(item, "Missing a letter between commas"));
+        }
+        let c = item
+            .parse::<char>()
+            .map_err(|_| err(item, "Expected a single letter, or letters separated by commas like `a,b`"))?;
+        if letters.contains(&c) {
+            return Err(err(item, &format!("Letter '{}' is listed twice", c)));
+        }
+        letters.push(c);
+    }
+
+    let (negated, rule) = match rule.strip_prefix('!') {
+        Some("") => return Err(err(rule, "Expected a range or count after `!`, like `!1-3`")),
+        Some(rest) => (true, rest),
+        None => (false, rule),
+    };
+
+    let (bounds, spans) = match rule.split_once('-') {
+        Some(("", _)) => {
+            return Err(err(rule, "Expected a number before `-`, like `1-3` or `2-`"));
+        }
+        Some((low, "")) => {
+            let dash = &rule[low.len()..];
+            let spans = BoundSpans { low: span_of(line, low), high: span_of(line, dash) };
+            (Bounds::AtLeast(number(low, "Invalid number in open range")?), spans)
+        }
+        Some((low, high)) => {
+            let spans = BoundSpans { low: span_of(line, low), high: span_of(line, high) };
+            let (low, high) = (number(low, "Invalid number")?, number(high, "Invalid number")?);
+            if high < low {
+                return Err(err(rule, &format!("Range {}-{} ends before it starts", low, high)));
+            }
+            (Bounds::Range(low, high), spans)
+        }
+        None => {
+            let spans = BoundSpans { low: span_of(line, rule), high: span_of(line, rule) };
+            (Bounds::Exactly(number(rule, "Expected a count like `3` or a range like `1-3`")?), spans)
+        }
+    };
+
+    Ok(ParsedLine { negated, bounds, letters, password, spans })
+}
+
+pub fn parse_lines(lines: &[String]) -> Result<Vec<ParsedLine>, Error> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(index, x)| parse_line(x).map_err(|err| err.with_line(index, x)))
+        .collect()
+}
+
+/// How one policy judged one line of an audit.
+#[derive(Debug, Clone)]
+pub struct PolicyResult {
+    pub policy: String,
+    pub valid: Result<bool, PolicyError>,
+    pub reason: String,
+}
+
+impl PolicyResult {
+    /// `pass`, `fail` or `error`.
+    pub fn status(&self) -> &'static str {
+        match self.valid {
+            Ok(true) => "pass",
+            Ok(false) => "fail",
+            Err(_) => "error",
+        }
+    }
+}
+
+/// One line of an audit: what it parsed to and how each policy judged it.
+/// Lines that don't parse get the parse error and no results.
+#[derive(Debug, Clone)]
+pub struct AuditRow {
+    pub index: usize,
+    pub text: String,
+    pub parsed: Result<ParsedLine, String>,
+    pub results: Vec<PolicyResult>,
+}
+
+/// Checks every line against every one of `policies`, keeping going past
+/// lines that don't parse or can't be checked.
+pub fn audit(policies: &[&dyn PasswordPolicy], lines: &[String]) -> Vec<AuditRow> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(index, text)| {
+            let parsed = parse_line(text).map_err(|err| err.message().to_string());
+            let results = match &parsed {
+                Ok(line) => policies
+                    .iter()
+                    .map(|policy| {
+                        let valid = policy.check(line);
+                        let reason = match policy.explain(line) {
+                            Ok(reason) => reason,
+                            Err(err) => err.to_string(),
+                        };
+                        PolicyResult { policy: policy.name().to_string(), valid, reason }
+                    })
+                    .collect(),
+                Err(_) => vec![],
+            };
+            AuditRow { index, text: text.clone(), parsed, results }
+        })
+        .collect()
+}
+
+/// How an audit report is written out.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AuditFormat {
+    Table,
+    Csv,
+    Json,
+}
+
+impl std::str::FromStr for AuditFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(AuditFormat::Table),
+            "csv" => Ok(AuditFormat::Csv),
+            "json" => Ok(AuditFormat::Json),
+            _ => Err(Error::new(format!("Unknown audit format: {} (expected table, csv or json)", s))),
+        }
+    }
+}
+
+/// The columns of an audit row: line number, policy fields, then a status
+/// and reason per policy. A line that doesn't parse has its error in the
+/// first reason column.
+fn audit_columns(row: &AuditRow, policies: usize) -> Vec<String> {
+    let mut columns = vec![(row.index + 1).to_string()];
+    match &row.parsed {
+        Ok(line) => {
+            columns.extend([line.rule(), line.letter_list(), line.password.clone()]);
+            for result in row.results.iter() {
+                columns.extend([result.status().to_string(), result.reason.clone()]);
+            }
+        }
+        Err(err) => {
+            columns.extend([String::new(), String::new(), row.text.clone()]);
+            for i in 0..policies {
+                let reason = if i == 0 { err.clone() } else { String::new() };
+                columns.extend(["error".to_string(), reason]);
+            }
+        }
+    }
+    columns
+}
+
+fn audit_header(policies: &[&dyn PasswordPolicy]) -> Vec<String> {
+    let mut header = ["line", "rule", "letters", "password"].map(String::from).to_vec();
+    for policy in policies {
+        header.extend([policy.name().to_string(), format!("{} reason", policy.name())]);
+    }
+    header
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Writes an audit as an aligned table, CSV with a header row, or a JSON
+/// array with one object per line.
+pub fn render_audit(policies: &[&dyn PasswordPolicy], rows: &[AuditRow], format: AuditFormat) -> String {
+    let header = audit_header(policies);
+    let table = rows.iter().map(|row| audit_columns(row, policies.len())).collect::<Vec<_>>();
+
+    match format {
+        AuditFormat::Table => {
+            let mut widths = header.iter().map(|h| h.chars().count()).collect::<Vec<_>>();
+            for columns in table.iter() {
+                for (width, column) in widths.iter_mut().zip(columns) {
+                    *width = (*width).max(column.chars().count());
+                }
+            }
+
+            let mut out = String::new();
+            for columns in std::iter::once(&header).chain(table.iter()) {
+                let cells = columns
+                    .iter()
+                    .zip(widths.iter())
+                    .map(|(column, width)| format!("{:<width$}", column, width = width))
+                    .collect::<Vec<_>>();
+                out.push_str(cells.join("  ").trim_end());
+                out.push('\n');
+            }
+            out
+        }
+        AuditFormat::Csv => {
+            let mut out = String::new();
+            for columns in std::iter::once(&header).chain(table.iter()) {
+                out.push_str(&columns.iter().map(|c| csv_field(c)).collect::<Vec<_>>().join(","));
+                out.push('\n');
+            }
+            out
+        }
+        AuditFormat::Json => {
+            let objects = rows
+                .iter()
+                .map(|row| {
+                    let parsed = match &row.parsed {
+                        Ok(line) => format!(
+                            "\"rule\":{},\"negated\":{},\"low\":{},\"high\":{},\"letters\":[{}],\"password\":{}",
+                            json::quote(&line.rule()),
+                            line.negated,
+                            line.bounds.low(),
+                            line.bounds.high().map_or("null".to_string(), |high| high.to_string()),
+                            line.letters.iter().map(|c| json::quote(&c.to_string())).collect::<Vec<_>>().join(","),
+                            json::quote(&line.password),
+                        ),
+                        Err(err) => format!("\"error\":{}", json::quote(err)),
+                    };
+                    let results = row
+                        .results
+                        .iter()
+                        .map(|result| {
+                            format!(
+                                "{{\"policy\":{},\"status\":{},\"reason\":{}}}",
+                                json::quote(&result.policy),
+                                json::quote(result.status()),
+                                json::quote(&result.reason),
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    format!(
+                        "{{\"line\":{},\"text\":{},{},\"policies\":[{}]}}",
+                        row.index + 1,
+                        json::quote(&row.text),
+                        parsed,
+                        results.join(","),
+                    )
+                })
+                .collect::<Vec<_>>();
+            format!("[{}]\n", objects.join(","))
+        }
+    }
+}
+
+/// Which lines [`filter_lines`] writes out.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Keep {
+    Passing,
+    Failing,
+}
+
+impl std::str::FromStr for Keep {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pass" => Ok(Keep::Passing),
+            "fail" => Ok(Keep::Failing),
+            _ => Err(Error::new(format!("Unknown lines to keep: {} (expected pass or fail)", s))),
+        }
+    }
+}
+
+/// What [`filter_lines`] does with a line that doesn't parse or can't be
+/// checked. Such a line is never written out.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OnError {
+    Skip,
+    Warn,
+    Fail,
+}
+
+impl std::str::FromStr for OnError {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "skip" => Ok(OnError::Skip),
+            "warn" => Ok(OnError::Warn),
+            "fail" => Ok(OnError::Fail),
+            _ => Err(Error::new(format!("Unknown error action: {} (expected skip, warn or fail)", s))),
+        }
+    }
+}
+
+/// Copies the lines of `reader` that `policy` passes, or fails, to `out` as
+/// they are read. Lines that don't parse or can't be checked are handled as
+/// `on_error` says, with warnings handed to `warn`. Returns whether every
+/// line passed; it stops early, without an error, if `out` is closed.
+pub fn filter_lines<R: BufRead, W: Write, F: FnMut(Error)>(
+    reader: R,
+    out: &mut W,
+    policy: &dyn PasswordPolicy,
+    keep: Keep,
+    on_error: OnError,
+    mut warn: F,
+) -> Result<bool, Error> {
+    let mut all_passed = true;
+    for (index, text) in reader.lines().enumerate() {
+        let text = text.map_err(|err| Error::new(format!("Failed to read line {}", index + 1)).caused_by(err))?;
+        let checked = parse_line(&text)
+            .map_err(|err| err.with_line(index, &text))
+            .and_then(|line| policy.check(&line).map_err(|err| err.at_line(index, &line, &text)));
+
+        let passed = match checked {
+            Ok(passed) => passed,
+            Err(err) => {
+                all_passed = false;
+                match on_error {
+                    OnError::Skip => {}
+                    OnError::Warn => warn(err),
+                    OnError::Fail => return Err(err),
+                }
+                continue;
+            }
+        };
+        all_passed &= passed;
+
+        if passed == (keep == Keep::Passing) {
+            match writeln!(out, "{}", text) {
+                Ok(()) => {}
+                Err(err) if err.kind() == io::ErrorKind::BrokenPipe => return Ok(all_passed),
+                Err(err) => return Err(Error::new("Failed to write output").caused_by(err)),
+            }
+        }
+    }
+
+    match out.flush() {
+        Err(err) if err.kind() != io::ErrorKind::BrokenPipe => Err(Error::new("Failed to write output").caused_by(err)),
+        _ => Ok(all_passed),
+    }
+}
+
+/// `size` password lines, with the number of lines valid under each policy
+/// counted from how each password was built rather than by validating it.
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let mut lines = vec![];
+    let (mut valid1, mut valid2) = (0, 0);
+
+    for _ in 0..size {
+        let lo = rng.between(1, 5) as usize;
+        let hi = rng.between(lo as i64 + 1, lo as i64 + 8) as usize;
+        let letter = (b'a' + rng.below(26) as u8) as char;
+        let len = rng.between(hi as i64, hi as i64 + 6) as usize;
+
+        // How many of the two positions hold the letter: 1 for a valid
+        // line under the second policy, 0 or 2 otherwise.
+        let at = *rng.choose(&[(true, false), (false, true), (false, false), (true, true)]);
+        let others = (0..len).filter(|i| *i != lo - 1 && *i != hi - 1).collect::<Vec<_>>();
+        let extra = rng.below(others.len() + 1);
+        let count = usize::from(at.0) + usize::from(at.1) + extra;
+
+        let mut slots = others;
+        rng.shuffle(&mut slots);
+        let mut password = (0..len)
+            .map(|_| loop {
+                let c = (b'a' + rng.below(26) as u8) as char;
+                if c != letter {
+                    break c;
+                }
+            })
+            .collect::<Vec<char>>();
+        for i in slots.into_iter().take(extra) {
+            password[i] = letter;
+        }
+        if at.0 {
+            password[lo - 1] = letter;
+        }
+        if at.1 {
+            password[hi - 1] = letter;
+        }
+
+        if (lo..=hi).contains(&count) {
+            valid1 += 1;
+        }
+        if at.0 != at.1 {
+            valid2 += 1;
+        }
+        lines.push(format!("{}-{} {}: {}", lo, hi, letter, password.into_iter().collect::<String>()));
+    }
+
+    Generated::new(lines, Some(valid1.into()), Some(valid2.into()))
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u32 = 2;
+    type Parsed = Vec<ParsedLine>;
+
+    fn parse(lines: &[String]) -> error::Result<Vec<ParsedLine>> {
+        parse_lines(lines)
+    }
+
+    fn part1(lines: &Vec<ParsedLine>) -> error::Result<Answer> {
+        Ok(count_valid(&SledRental, lines)?.into())
+    }
+
+    fn part2(lines: &Vec<ParsedLine>) -> error::Result<Answer> {
+        Ok(count_valid(&Toboggan, lines)?.into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    fn file(lines: &[&str]) -> Vec<ParsedLine> {
+        parse_lines(&texts(lines)).unwrap()
+    }
+
+    #[test]
+    fn policy_errors_quote_the_input_line() {
+        let lines = texts(&["1-3 a: abc", "+01-9 b: cdefg"]);
+        let err = count_valid(&Toboggan, &parse_lines(&lines).unwrap()).unwrap_err();
+        assert_eq!(err.line(), Some(2));
+        assert_eq!(err.span(), Some(4..5));
+        assert!(err.quoting(&lines).to_string().contains("2 | +01-9 b: cdefg"));
+    }
+
+    #[test]
+    fn policy_errors_point_at_the_low_number() {
+        let err = count_valid(&Toboggan, &file(&["!0-2 a: ab"])).unwrap_err();
+        assert_eq!(err.span(), Some(1..2));
+    }
+
+    fn parse_err(line: &str) -> (String, Option<Range<usize>>) {
+        let err = parse_line(line).unwrap_err();
+        (err.message().to_string(), err.span())
+    }
+
+    #[test]
+    fn parses_the_puzzle_format() {
+        let line = parse_line("1-3 a: abcde").unwrap();
+        assert_eq!((line.negated, line.bounds, line.letters.clone()), (false, Bounds::Range(1, 3), vec!['a']));
+        assert_eq!(line.password, "abcde");
+        assert_eq!(line.to_line(), "1-3 a: abcde");
+    }
+
+    #[test]
+    fn parses_the_extended_forms() {
+        assert_eq!(parse_line("1-3 a,b: x").unwrap().letters, vec!['a', 'b']);
+        assert_eq!(parse_line("2- a: x").unwrap().bounds, Bounds::AtLeast(2));
+        assert_eq!(parse_line("3 a: x").unwrap().bounds, Bounds::Exactly(3));
+
+        let line = parse_line("!1-3 a: x").unwrap();
+        assert!(line.negated);
+        assert_eq!(line.bounds, Bounds::Range(1, 3));
+        assert_eq!(line.spans, BoundSpans { low: 1..2, high: 3..4 });
+    }
+
+    #[test]
+    fn rejects_bad_letter_lists() {
+        assert_eq!(parse_err("1-3 a,: x").0, "Missing a letter between commas");
+        assert_eq!(parse_err("1-3 a,,b: x"), ("Missing a letter between commas".to_string(), Some(6..6)));
+        assert!(parse_err("1-3 ab: x").0.starts_with("Expected a single letter"));
+        assert_eq!(parse_err("1-3 a,a: x"), ("Letter 'a' is listed twice".to_string(), Some(6..7)));
+        assert!(parse_err("1-3 : x").0.starts_with("Missing the letter"));
+    }
+
+    #[test]
+    fn rejects_bad_bounds() {
+        assert!(parse_err("! a: x").0.starts_with("Expected a range or count after `!`"));
+        assert!(parse_err("-3 a: x").0.starts_with("Expected a number before `-`"));
+        assert_eq!(parse_err("x- a: x").0, "Invalid number in open range");
+        assert!(parse_err("q a: x").0.starts_with("Expected a count"));
+        assert_eq!(parse_err("1-y a: x"), ("Invalid number".to_string(), Some(2..3)));
+        assert_eq!(parse_err("1--3 a: x"), ("Numbers in a policy can't be negative".to_string(), Some(2..4)));
+        assert_eq!(parse_err("3-1 a: x"), ("Range 3-1 ends before it starts".to_string(), Some(0..3)));
+        assert!(parse_err("1-3: x").0.starts_with("Expected `"));
+    }
+
+    fn audit_of(lines: &[&str], format: AuditFormat) -> String {
+        let policies: [&dyn PasswordPolicy; 2] = [&SledRental, &Toboggan];
+        render_audit(&policies, &audit(&policies, &texts(lines)), format)
+    }
+
+    #[test]
+    fn audit_renders_a_table() {
+        let table = audit_of(&["1-3 a: abcde", "3 b: cdb", "1-x a: b"], AuditFormat::Table);
+        let expected = [
+            "line  rule  letters  password  sled-rental  sled-rental reason                             toboggan  toboggan reason",
+            "1     1-3   a        abcde     pass         letter 'a' appears 1 time, required 1-3        pass      letter 'a' is at position 1 only",
+            "2     3     b        cdb       fail         letter 'b' appears 1 time, required exactly 3  pass      letter 'b' is at position 3",
+            "3                    1-x a: b  error        Invalid number                                 error",
+        ];
+        assert_eq!(table, format!("{}\n", expected.join("\n")));
+    }
+
+    #[test]
+    fn audit_renders_csv() {
+        let csv = audit_of(&["1-3 a: a,\"b\"", "1-x a: b"], AuditFormat::Csv);
+        let expected = [
+            "line,rule,letters,password,sled-rental,sled-rental reason,toboggan,toboggan reason",
+            "1,1-3,a,\"a,\"\"b\"\"\",pass,\"letter 'a' appears 1 time, required 1-3\",pass,letter 'a' is at position 1 only",
+            "2,,,1-x a: b,error,Invalid number,error,",
+        ];
+        assert_eq!(csv, format!("{}\n", expected.join("\n")));
+    }
+
+    #[test]
+    fn audit_renders_json() {
+        let json = audit_of(&["!1-3 a,b: ab", "3 c: ccc", "2- a: x", "1-x a: b"], AuditFormat::Json);
+        let expected = [
+            r#"{"line":1,"text":"!1-3 a,b: ab","rule":"!1-3","negated":true,"low":1,"high":3,"letters":["a","b"],"password":"ab","policies":[{"policy":"sled-rental","status":"fail","reason":"letters 'a', 'b' appear 2 times, required not 1-3"},{"policy":"toboggan","status":"error","reason":"Position 3 is past the end of the password, which has 2 characters"}]}"#,
+            r#"{"line":2,"text":"3 c: ccc","rule":"3","negated":false,"low":3,"high":3,"letters":["c"],"password":"ccc","policies":[{"policy":"sled-rental","status":"pass","reason":"letter 'c' appears 3 times, required exactly 3"},{"policy":"toboggan","status":"pass","reason":"letter 'c' is at position 3"}]}"#,
+            r#"{"line":3,"text":"2- a: x","rule":"2-","negated":false,"low":2,"high":null,"letters":["a"],"password":"x","policies":[{"policy":"sled-rental","status":"fail","reason":"letter 'a' appears 0 times, required at least 2"},{"policy":"toboggan","status":"error","reason":"Open range 2- has no second position"}]}"#,
+            r#"{"line":4,"text":"1-x a: b","error":"Invalid number","policies":[]}"#,
+        ];
+        assert_eq!(json, format!("[{}]\n", expected.join(",")));
+    }
+
+    fn filter(text: &str, keep: Keep, on_error: OnError) -> (Result<bool, Error>, String, Vec<usize>) {
+        let mut out = vec![];
+        let mut warned = vec![];
+        let warn = |err: Error| warned.push(err.line().unwrap());
+        let result = filter_lines(text.as_bytes(), &mut out, &Toboggan, keep, on_error, warn);
+        (result, String::from_utf8(out).unwrap(), warned)
+    }
+
+    const FILTER_INPUT: &str = "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc\n1-x a: b\n1-9 a: ab\n";
+
+    #[test]
+    fn filter_keeps_passing_or_failing_lines() {
+        let (passed, out, _) = filter("1-3 a: abcde\n2-9 c: acbdefghi\n", Keep::Passing, OnError::Fail);
+        assert!(passed.unwrap());
+        assert_eq!(out, "1-3 a: abcde\n2-9 c: acbdefghi\n");
+
+        let (passed, out, _) = filter(FILTER_INPUT, Keep::Passing, OnError::Skip);
+        assert!(!passed.unwrap());
+        assert_eq!(out, "1-3 a: abcde\n");
+
+        let (passed, out, _) = filter(FILTER_INPUT, Keep::Failing, OnError::Skip);
+        assert!(!passed.unwrap());
+        assert_eq!(out, "1-3 b: cdefg\n2-9 c: ccccccccc\n");
+    }
+
+    #[test]
+    fn filter_handles_bad_lines_as_asked() {
+        let (passed, out, warned) = filter(FILTER_INPUT, Keep::Passing, OnError::Skip);
+        assert_eq!((passed.unwrap(), out.as_str(), warned), (false, "1-3 a: abcde\n", vec![]));
+
+        let (passed, out, warned) = filter(FILTER_INPUT, Keep::Passing, OnError::Warn);
+        assert_eq!((passed.unwrap(), out.as_str(), warned), (false, "1-3 a: abcde\n", vec![4, 5]));
+
+        let (passed, out, warned) = filter(FILTER_INPUT, Keep::Failing, OnError::Fail);
+        assert_eq!(passed.unwrap_err().line(), Some(4));
+        assert_eq!((out.as_str(), warned), ("1-3 b: cdefg\n2-9 c: ccccccccc\n", vec![]));
+    }
+
+    /// Takes `room` lines, then acts like a pipe whose reader has gone.
+    struct ClosedPipe {
+        room: usize,
+        lines: usize,
+    }
+
+    impl Write for ClosedPipe {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.lines == self.room {
+                return Err(io::ErrorKind::BrokenPipe.into());
+            }
+            self.lines += buf.iter().filter(|b| **b == b'\n').count();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn filter_stops_quietly_when_the_pipe_closes() {
+        let mut out = ClosedPipe { room: 1, lines: 0 };
+        let text = "1-3 a: abcde\n1-3 a: abcde\n1-x a: b\n";
+        let warn = |err: Error| panic!("read past the closed pipe: {}", err);
+        let passed = filter_lines(text.as_bytes(), &mut out, &Toboggan, Keep::Passing, OnError::Warn, warn);
+        assert!(passed.unwrap());
+        assert_eq!(out.lines, 1);
+    }
+
+    fn stream(text: &str, policies: &[&dyn PasswordPolicy], workers: usize, chunk: usize) -> Result<StreamTotals, Error> {
+        validate_stream(io::Cursor::new(text), policies, workers, chunk)
+    }
+
+    #[test]
+    fn stream_totals_match_count_valid() {
+        let mut rng = Rng::new(7);
+        let text = generate(&mut rng, 1000).input;
+        let lines = text.lines().map(|line| line.to_string()).collect::<Vec<_>>();
+        let file = parse_lines(&lines).unwrap();
+
+        let totals = stream(&text, &[&SledRental, &Toboggan], 3, 7).unwrap();
+        assert_eq!(totals.lines, 1000);
+        assert_eq!(totals.valid, vec![count_valid(&SledRental, &file).unwrap(), count_valid(&Toboggan, &file).unwrap()]);
+    }
+
+    #[test]
+    fn stream_reports_the_earliest_failing_line() {
+        let mut lines = vec!["1-3 a: abcde"; 1000];
+        lines[499] = "1-x a: b";
+        lines[899] = "bad";
+        for workers in 1..=4 {
+            let err = stream(&lines.join("\n"), &[&SledRental], workers, 3).unwrap_err();
+            assert_eq!(err.line(), Some(500));
+        }
+    }
+
+    #[test]
+    fn stream_survives_a_panicking_policy() {
+        let policy = FnPolicy::new("panics", |_| panic!("boom"));
+        let text = vec!["1-3 a: abcde"; 100_000].join("\n");
+
+        let err = stream(&text, &[&policy], 2, 10).unwrap_err();
+        assert_eq!(err.line(), Some(1));
+        assert_eq!(err.message(), "Policy panics panicked: boom");
+    }
+}
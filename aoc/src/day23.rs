@@ -0,0 +1,352 @@
+use std::cmp;
+use std::fmt;
+use std::collections::LinkedList;
+use std::convert::TryInto;
+
+use crate::diff::Implementation;
+use crate::error::{self, Error};
+use crate::generate::Generated;
+use crate::rng::Rng;
+use crate::solution::{Answer, Solution};
+
+#[derive(Debug, Clone)]
+pub struct Cup {
+    pub label: u8,
+    next: Option<Box<Cup>>,
+}
+
+impl Cup {
+    pub fn new(label: u8) -> Self {
+        Self {
+            label,
+            next: None,
+        }
+    }
+
+    pub fn insert(&mut self, label: u8) -> Option<&mut Box<Cup>> {
+        self.next = Some(Box::new(Self {
+            label,
+            next: self.next.clone(),
+        }));
+        self.next.as_mut()
+    }
+}
+
+impl fmt::Display for Cup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        match self.next {
+            Some(_) => write!(f, "{} {}", self.label, self.next.as_ref().unwrap()),
+            _ => write!(f, "{}", self.label),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Cups {
+    pub head: Box<Cup>,
+    pub max: u8,
+}
+
+impl Cups {
+    pub fn new(labels: &[u8]) -> Result<Self, Error> {
+        let mut max = *labels.first().ok_or("Not enough labels")?;
+        let mut min = max;
+
+        let mut head = Box::new(Cup::new(max));
+        let mut current = &mut head;
+
+        for label in &labels[1..] {
+            let label = *label;
+            max = cmp::max(max, label);
+            min = cmp::min(min, label);
+            current = current
+                .insert(label)
+                .ok_or_else(|| format!("Failed to insert: {}", label))?;
+        }
+
+        Ok(Cups {
+            head,
+            max,
+        })
+    }
+
+    pub fn shuffle(&mut self) -> Result<(), Error> {
+        /*
+        let mut current_head = &self.head;
+        let mut skip_cups = &current_head.next;
+        let mut new_next = skip_cups;
+        for _ in 0..2 {
+            new_next = match new_next {
+                Some(next) => &Some(next),
+                _ => &None,
+            };
+        }
+        self.head = current_head;
+        self.head.next = new_next;
+        */
+        Ok(())
+    }
+}
+
+impl fmt::Display for Cups {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "cups: {}", self.head)?;
+        /*
+        match self.head.next {
+            Some(_) => write!(f, "{} {}", self.head.label, self.head.next.unwrap()),
+            _ => write!(f, "{}", self.head.label),
+        };
+        */
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct CrabCups {
+    list: LinkedList<u8>,
+    max: u8,
+}
+
+impl CrabCups {
+    pub fn new(labels: &[u8]) -> Result<Self, Error> {
+        let mut cups = Self {
+            list: LinkedList::new(),
+            max: labels.len().try_into().map_err(|_| "Too many labels")?,
+        };
+
+        cups.list.extend(labels);
+
+        Ok(cups)
+    }
+
+    pub fn shuffle(&mut self) {
+        let (target, mut head, mut picked_up) = {
+            let mut prev_head = self.list.split_off(0);
+            let mut picked_up = prev_head.split_off(1);
+            let mut new_head = picked_up.split_off(3);
+            let target = *prev_head.front().unwrap();
+            new_head.append(&mut prev_head);
+
+            (target, new_head, picked_up)
+        };
+
+        let dest = {
+            let mut dest = match target {
+                1 => self.max,
+                val => val - 1,
+            };
+
+            let set = picked_up.iter().collect::<Vec<_>>();
+
+            while set.contains(&&dest) {
+                dest = match dest {
+                    1 => self.max,
+                    val => val - 1,
+                };
+            }
+
+            dest
+        };
+
+        let mut index = 0;
+        for (i, label) in head.iter().enumerate() {
+            if dest == *label {
+                index = i;
+                break;
+            }
+        }
+
+        self.list = {
+            let mut split = head.split_off(index + 1);
+            head.append(&mut picked_up);
+            head.append(&mut split);
+            head
+        };
+    }
+
+    pub fn reorder(&mut self) {
+        let mut index = 0;
+        for (i, val) in self.list.iter().enumerate() {
+            if *val == 1 {
+                index = i;
+                break;
+            }
+        }
+
+        self.list = {
+            let mut new_head = self.list.split_off(index + 1);
+            new_head.append(&mut self.list);
+            new_head
+        };
+    }
+
+    pub fn result(&self) -> String {
+        let mut vec = self.list
+            .iter()
+            .map(|x| format!("{}", x))
+            .collect::<Vec<_>>();
+        vec.pop();
+
+        vec.join("")
+    }
+}
+
+impl fmt::Display for CrabCups {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "cups:")?;
+        for label in self.list.iter() {
+            write!(f, " {}", label)?;
+        }
+        Ok(())
+    }
+}
+
+/// Plays `moves` moves on a plain `Vec`, current cup first, and returns the
+/// labels after cup 1. Slow but simple enough to trust.
+pub fn play_reference(labels: &[u8], moves: usize) -> String {
+    let max = labels.len() as u8;
+    let mut cups = labels.to_vec();
+
+    for _ in 0..moves {
+        let current = cups[0];
+        let picked = cups.drain(1..4).collect::<Vec<_>>();
+        let mut dest = if current == 1 { max } else { current - 1 };
+        while picked.contains(&dest) {
+            dest = if dest == 1 { max } else { dest - 1 };
+        }
+
+        let at = cups.iter().position(|cup| *cup == dest).unwrap_or_default();
+        cups.splice(at + 1..at + 1, picked);
+        cups.rotate_left(1);
+    }
+
+    let one = cups.iter().position(|cup| *cup == 1).unwrap_or_default();
+    cups.rotate_left(one);
+    cups[1..].iter().map(|cup| cup.to_string()).collect()
+}
+
+/// A random order of the cups `1..=size`, with between 5 and 9 cups so every
+/// label stays a single digit. Only part 1 is known.
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let mut labels = (1..=size.clamp(5, 9) as u8).collect::<Vec<_>>();
+    rng.shuffle(&mut labels);
+
+    let part1 = play_reference(&labels, 100);
+    let line = labels.iter().map(|label| label.to_string()).collect();
+    Generated::new(vec![line], Some(part1.into()), None)
+}
+
+fn part1_reference(lines: &[String]) -> error::Result<Answer> {
+    Ok(play_reference(&Day23::parse(lines)?, 100).into())
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u32 = 23;
+    // Part 2 isn't solved yet, so only part 1 is offered.
+    const PARTS: u8 = 1;
+    type Parsed = Vec<u8>;
+
+    // `Cups` isn't listed: its `shuffle` doesn't move any cups yet.
+    const IMPLEMENTATIONS: &'static [Implementation] = &[
+        Implementation { name: "vec-reference", part: 1, solve: part1_reference },
+    ];
+
+    fn parse(lines: &[String]) -> error::Result<Vec<u8>> {
+        let first = lines.first().ok_or("Not enough lines")?;
+
+        let labels = first
+            .chars()
+            .enumerate()
+            .map(|(column, c)| match c.to_digit(10) {
+                Some(label) => Ok(label as u8),
+                None => Err(Error::at_line(format!("Invalid cup label: {}", c), 0, first)
+                    .with_span(column..column + 1)),
+            })
+            .collect::<error::Result<Vec<u8>>>()?;
+
+        // A move picks up three cups and needs one more to put them after.
+        if labels.len() < 4 {
+            return Err(Error::at_line(format!("Need at least 4 cups, found {}", labels.len()), 0, first)
+                .with_span(0..labels.len()));
+        }
+
+        let count = labels.len() as u8;
+        for (column, &label) in labels.iter().enumerate() {
+            if label == 0 || label > count {
+                let missing = (1..=count).find(|label| !labels.contains(label)).unwrap_or(count);
+                return Err(Error::at_line(
+                    format!(
+                        "Cup label {} is out of range: {} cups are labelled 1 to {}, and {} is missing",
+                        label, count, count, missing
+                    ),
+                    0,
+                    first,
+                )
+                .with_span(column..column + 1));
+            }
+            if labels[..column].contains(&label) {
+                return Err(Error::at_line(format!("Cup label {} appears twice", label), 0, first)
+                    .with_span(column..column + 1));
+            }
+        }
+
+        Ok(labels)
+    }
+
+    fn part1(cups: &Vec<u8>) -> error::Result<Answer> {
+        let loops = 100;
+
+        let mut cups = CrabCups::new(&cups[..])?;
+
+        for _ in 0..loops {
+            cups.shuffle();
+        }
+
+        cups.reorder();
+
+        Ok(cups.result().into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> error::Result<Vec<u8>> {
+        Day23::parse(&[line.to_string()])
+    }
+
+    #[test]
+    fn parses_a_full_set_of_labels() {
+        assert_eq!(parse("3412").unwrap(), vec![3, 4, 1, 2]);
+    }
+
+    #[test]
+    fn rejects_label_zero() {
+        let err = parse("0123456789").unwrap_err();
+        assert!(err.message().contains("Cup label 0 is out of range"));
+        assert_eq!(err.span(), Some(0..1));
+    }
+
+    #[test]
+    fn rejects_too_few_cups() {
+        assert!(parse("12").unwrap_err().message().contains("at least 4 cups"));
+    }
+
+    #[test]
+    fn rejects_duplicate_and_missing_labels() {
+        let err = parse("1231").unwrap_err();
+        assert_eq!(err.message(), "Cup label 1 appears twice");
+        assert_eq!(err.span(), Some(3..4));
+
+        let err = parse("1236").unwrap_err();
+        assert!(err.message().contains("4 is missing"));
+        assert_eq!(err.span(), Some(3..4));
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::{HashSet, HashMap};
+use std::convert::From;
+use std::str::FromStr;
+
+use crate::diff::Implementation;
+use crate::error::{self, span_of, Error};
+use crate::generate::Generated;
+use crate::rng::Rng;
+use crate::solution::{Answer, Solution};
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Hash)]
+pub struct Pos {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Pos {
+    pub fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    pub fn add(&mut self, pos: &Pos) {
+        self.x += pos.x;
+        self.y += pos.y;
+    }
+}
+
+impl Eq for Pos {
+}
+
+#[derive(Debug)]
+pub struct Tile {
+    inner: Pos,
+    orig: String,
+}
+
+impl FromStr for Tile {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Tile {
+            inner: match s {
+                "e" => Ok(Pos::new(2, 0)),
+                "w" => Ok(Pos::new(-2, 0)),
+                "se" => Ok(Pos::new(1, 1)),
+                "sw" => Ok(Pos::new(-1, 1)),
+                "ne" => Ok(Pos::new(1, -1)),
+                "nw" => Ok(Pos::new(-1,-1)),
+                _ => Err(Error::new(format!("Invalid tile: {}", s))),
+            }?,
+            orig: s.to_string(),
+        })
+    }
+}
+
+impl From<&Tile> for Pos {
+    fn from(tile: &Tile) -> Pos {
+        tile.inner
+    }
+}
+
+pub fn flip_initial_tiles(lines: &[String]) -> Result<HashSet<Pos>, Error> {
+    let mut black_tiles: HashSet<Pos> = HashSet::new();
+
+    for (index, text) in lines.iter().enumerate() {
+        let mut line: &str = &text[..];
+        let mut pos = Pos::default();
+        loop {
+            if line.is_empty() {
+                break;
+            }
+
+            let tile = match line.get(..2).map(|dir| dir.parse::<Tile>()) {
+                Some(Ok(tile)) => tile,
+                _ => {
+                    let dir = &line[..line.chars().next().map_or(1, char::len_utf8)];
+                    dir.parse::<Tile>()
+                        .map_err(|err| err.with_span(span_of(text, dir)).with_line(index, text))?
+                }
+            };
+
+            pos.add(&Pos::from(&tile));
+
+            line = &line[tile.orig.len()..];
+        };
+
+        if black_tiles.contains(&pos) {
+            black_tiles.remove(&pos);
+        } else {
+            black_tiles.insert(pos);
+        }
+    }
+
+    Ok(black_tiles)
+}
+
+pub fn get_neighbours(tile: &Pos) -> [Pos; 6] {
+    let mut neighbours = [Pos::default(); 6];
+    let dirs = &["e", "w", "se", "sw", "ne", "nw"];
+    for (i, dir) in dirs.iter().enumerate() {
+        let neighbour: Tile = dir.parse().unwrap();
+
+        let mut pos = *tile;
+        pos.add(&Pos::from(&neighbour));
+
+        neighbours[i] = pos;
+    }
+    neighbours
+}
+
+pub fn flip_tiles(black_tiles: &mut HashSet<Pos>) -> Result<(), Error> {
+    let mut black_tiles_to_flip: Vec<Pos> = vec![];
+    let mut white_tiles: HashMap<Pos, u8> = HashMap::new();
+
+    for tile in black_tiles.iter() {
+        let mut black_neighbour_count = 0;
+        let neighbours = get_neighbours(tile);
+
+        for n in neighbours.iter() {
+            if black_tiles.contains(n) {
+                black_neighbour_count += 1;
+            } else {
+                let count = white_tiles.entry(*n).or_insert(0);
+                *count += 1;
+            }
+        }
+
+        if black_neighbour_count == 0 || black_neighbour_count > 2 {
+            black_tiles_to_flip.push(*tile);
+        }
+
+    }
+
+    for tile in black_tiles_to_flip.iter() {
+        black_tiles.remove(tile);
+    }
+
+    for (tile, count) in white_tiles.iter() {
+        if *count == 2 {
+            black_tiles.insert(*tile);
+        }
+    }
+
+    Ok(())
+}
+
+/// One day of [`flip_tiles`], done by visiting every cell of the bounding
+/// box around the black tiles, plus a margin of one tile.
+pub fn flip_tiles_scan(black_tiles: &HashSet<Pos>) -> HashSet<Pos> {
+    let mut flipped = HashSet::new();
+    if black_tiles.is_empty() {
+        return flipped;
+    }
+
+    let min_x = black_tiles.iter().map(|tile| tile.x).min().unwrap_or_default();
+    let max_x = black_tiles.iter().map(|tile| tile.x).max().unwrap_or_default();
+    let min_y = black_tiles.iter().map(|tile| tile.y).min().unwrap_or_default();
+    let max_y = black_tiles.iter().map(|tile| tile.y).max().unwrap_or_default();
+
+    for y in min_y - 1..=max_y + 1 {
+        for x in min_x - 2..=max_x + 2 {
+            // Only cells with an even x + y are tiles.
+            if (x + y).rem_euclid(2) != 0 {
+                continue;
+            }
+
+            let tile = Pos::new(x, y);
+            let black = [(2, 0), (-2, 0), (1, 1), (-1, 1), (1, -1), (-1, -1)]
+                .iter()
+                .filter(|(dx, dy)| black_tiles.contains(&Pos::new(x + dx, y + dy)))
+                .count();
+            if black == 2 || (black == 1 && black_tiles.contains(&tile)) {
+                flipped.insert(tile);
+            }
+        }
+    }
+
+    flipped
+}
+
+fn part2_scan(lines: &[String]) -> error::Result<Answer> {
+    let mut black_tiles = flip_initial_tiles(lines)?;
+    for _ in 0..100 {
+        black_tiles = flip_tiles_scan(&black_tiles);
+    }
+    Ok(black_tiles.len().into())
+}
+
+const AXIAL: [(&str, (i32, i32)); 6] = [
+    ("e", (1, 0)),
+    ("w", (-1, 0)),
+    ("ne", (1, -1)),
+    ("nw", (0, -1)),
+    ("se", (0, 1)),
+    ("sw", (-1, 1)),
+];
+
+/// `size` random paths. Some revisit an earlier tile by shuffling that
+/// path's steps, so tiles get flipped back. The answers come from tracking
+/// the tiles in axial coordinates, independent of [`Pos`] and
+/// [`flip_tiles`].
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let mut paths: Vec<Vec<usize>> = vec![];
+    let mut black: HashSet<(i32, i32)> = HashSet::new();
+
+    for _ in 0..size {
+        let path = if paths.is_empty() || rng.chance(0.7) {
+            let len = rng.between(1, 20);
+            (0..len).map(|_| rng.below(AXIAL.len())).collect()
+        } else {
+            let mut path = rng.choose(&paths).clone();
+            rng.shuffle(&mut path);
+            path
+        };
+
+        let tile = path.iter().fold((0, 0), |(q, r), step| (q + AXIAL[*step].1 .0, r + AXIAL[*step].1 .1));
+        if !black.remove(&tile) {
+            black.insert(tile);
+        }
+        paths.push(path);
+    }
+
+    let part1 = black.len();
+    for _ in 0..100 {
+        let mut counts: HashMap<(i32, i32), usize> = HashMap::new();
+        for (q, r) in black.iter() {
+            for (_, (dq, dr)) in AXIAL.iter() {
+                *counts.entry((q + dq, r + dr)).or_default() += 1;
+            }
+        }
+        black = counts
+            .into_iter()
+            .filter(|(tile, count)| *count == 2 || (*count == 1 && black.contains(tile)))
+            .map(|(tile, _)| tile)
+            .collect();
+    }
+
+    let lines = paths
+        .iter()
+        .map(|path| path.iter().map(|step| AXIAL[*step].0).collect())
+        .collect();
+    Generated::new(lines, Some(part1.into()), Some(black.len().into()))
+}
+
+pub struct Day24;
+
+impl Solution for Day24 {
+    const DAY: u32 = 24;
+    type Parsed = HashSet<Pos>;
+
+    const IMPLEMENTATIONS: &'static [Implementation] = &[
+        Implementation { name: "grid-scan", part: 2, solve: part2_scan },
+    ];
+
+    fn parse(lines: &[String]) -> error::Result<HashSet<Pos>> {
+        flip_initial_tiles(lines)
+    }
+
+    fn part1(black_tiles: &HashSet<Pos>) -> error::Result<Answer> {
+        Ok(black_tiles.len().into())
+    }
+
+    fn part2(black_tiles: &HashSet<Pos>) -> error::Result<Answer> {
+        let mut black_tiles = black_tiles.clone();
+        let target = 100;
+
+        for _ in 0..target {
+            flip_tiles(&mut black_tiles)?;
+        }
+
+        Ok(black_tiles.len().into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
@@ -0,0 +1,145 @@
+use crate::error::{self, Error};
+use crate::generate::Generated;
+use crate::rng::Rng;
+use crate::solution::{Answer, Solution};
+
+/// Keys are worked out modulo this prime.
+pub const MODULUS: usize = 20201227;
+
+pub fn transform_subject(subject_number: usize, loop_size: usize) -> usize {
+    let mut target = 1;
+
+    for _ in 0..loop_size {
+        target = target * subject_number % 20201227;
+    }
+
+    target
+}
+
+pub fn discover_loop_size(key: usize, subject_number: usize) -> usize {
+    let mut loop_size = 0;
+    let mut target = 1;
+    loop {
+        loop_size += 1;
+        target = target * subject_number % 20201227;
+
+        if target == key {
+            break loop_size;
+        }
+    }
+}
+
+pub fn find_encryption_key(card_public_key: usize, door_public_key: usize) -> usize {
+    let card_loop_size = discover_loop_size(card_public_key, 7);
+    let door_loop_size = discover_loop_size(door_public_key, 7);
+
+    let enc_key1 = transform_subject(card_public_key, door_loop_size);
+    let enc_key2 = transform_subject(door_public_key, card_loop_size);
+
+    assert!(enc_key1 == enc_key2, "encryption keys dont match: {} != {}", enc_key1, enc_key2);
+
+    enc_key1
+}
+
+fn mod_pow(mut base: u64, mut exp: u64) -> u64 {
+    let mut result = 1;
+    base %= 20201227;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % 20201227;
+        }
+        base = base * base % 20201227;
+        exp >>= 1;
+    }
+    result
+}
+
+/// Public keys for random loop sizes up to `size * 10_000`. The key is
+/// worked out by square and multiply, not by looping.
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let max = (size.max(1) as i64).saturating_mul(10_000).min(20201225);
+    let card_loop_size = rng.between(1, max) as u64;
+    let door_loop_size = rng.between(1, max) as u64;
+
+    let card_public_key = mod_pow(7, card_loop_size);
+    let door_public_key = mod_pow(7, door_loop_size);
+    let key = mod_pow(card_public_key, door_loop_size);
+
+    Generated::new(
+        vec![card_public_key.to_string(), door_public_key.to_string()],
+        Some(key.into()),
+        None,
+    )
+}
+
+pub struct Day25;
+
+impl Solution for Day25 {
+    const DAY: u32 = 25;
+    const PARTS: u8 = 1;
+    type Parsed = (usize, usize);
+
+    fn parse(lines: &[String]) -> error::Result<(usize, usize)> {
+        let keys = lines.get(..2).ok_or("Expected two public keys")?;
+
+        let parse_key = |index: usize, name: &str| {
+            let key = &keys[index];
+            let span = 0..key.chars().count();
+            let value = key.parse::<usize>().map_err(|err| {
+                Error::at_line(format!("Invalid {} public key", name), index, key)
+                    .with_span(span.clone())
+                    .caused_by(err)
+            })?;
+
+            // Every key in range is some power of 7; any other never turns up.
+            if !(1..MODULUS).contains(&value) {
+                return Err(Error::at_line(
+                    format!("The {} public key must be between 1 and {}", name, MODULUS - 1),
+                    index,
+                    key,
+                )
+                .with_span(span));
+            }
+            Ok(value)
+        };
+
+        let card_public_key = parse_key(0, "card")?;
+        let door_public_key = parse_key(1, "door")?;
+
+        Ok((card_public_key, door_public_key))
+    }
+
+    fn part1(keys: &(usize, usize)) -> error::Result<Answer> {
+        let (card_public_key, door_public_key) = *keys;
+        Ok(find_encryption_key(card_public_key, door_public_key).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(card: &str, door: &str) -> error::Result<(usize, usize)> {
+        Day25::parse(&[card.to_string(), door.to_string()])
+    }
+
+    #[test]
+    fn rejects_keys_out_of_range() {
+        let err = parse("0", "5").unwrap_err();
+        assert_eq!(err.line(), Some(1));
+        assert_eq!(err.span(), Some(0..1));
+
+        let err = parse("5", "20201227").unwrap_err();
+        assert_eq!(err.line(), Some(2));
+        assert_eq!(err.span(), Some(0..8));
+    }
+
+    #[test]
+    fn accepts_the_example_keys() {
+        assert_eq!(parse("5764801", "17807724").unwrap(), (5764801, 17807724));
+    }
+}
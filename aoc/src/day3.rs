@@ -0,0 +1,383 @@
+use std::fmt::{self, Display};
+use std::iter;
+use std::thread;
+
+use crate::error::{self, Error};
+use crate::generate::Generated;
+use crate::rng::Rng;
+use crate::solution::{Answer, Solution};
+
+/// One square of the map.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Cell {
+    Open,
+    Tree,
+}
+
+impl Cell {
+    pub fn from_char(c: char) -> Self {
+        if is_tree(c) {
+            Cell::Tree
+        } else {
+            Cell::Open
+        }
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Cell::Open => '.',
+            Cell::Tree => '#',
+        }
+    }
+}
+
+/// The map as one grid of cells, row after row, all `width` wide.
+#[derive(Debug)]
+pub struct TreeMap {
+    cells: Vec<Cell>,
+    width: usize,
+    height: usize,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Pos {
+    pub x: usize,
+    pub y: usize,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Slope(pub usize, pub usize);
+
+impl Pos {
+    pub fn new(x: usize, y: usize) -> Self {
+        Pos { x, y }
+    }
+
+    pub fn add(&self, slope: &Slope) -> Self {
+        Self { x: self.x + slope.0, y: self.y + slope.1 }
+    }
+}
+
+pub fn is_tree(t: char) -> bool {
+    matches!(t, '#')
+}
+
+impl TreeMap {
+    /// A map from rows of `.` and `#`, all as wide as the first. Empty,
+    /// ragged or unknown squares are errors pointing at the line and column.
+    pub fn new(lines: &[String]) -> Result<Self, Error> {
+        let width = match lines.first() {
+            Some(line) => line.chars().count(),
+            None => return Err(Error::new("The map has no rows")),
+        };
+
+        for (index, line) in lines.iter().enumerate() {
+            let len = line.chars().count();
+            if len == 0 {
+                return Err(Error::at_line("Row is empty", index, line));
+            }
+            if let Some((column, c)) = line.chars().enumerate().find(|(_, c)| !matches!(c, '.' | '#')) {
+                return Err(Error::at_line(format!("Unknown square '{}', expected '.' or '#'", c), index, line)
+                    .with_span(column..column + 1));
+            }
+            if len != width {
+                let span = if len > width { width..len } else { len..len + 1 };
+                return Err(Error::at_line(
+                    format!("Row is {} squares wide, but the first row is {}", len, width),
+                    index,
+                    line,
+                )
+                .with_span(span));
+            }
+        }
+
+        Ok(TreeMap::lenient(lines))
+    }
+
+    /// A map from anything: it is as wide as its widest row, shorter rows are
+    /// padded with open squares to match, and anything but `#` is open.
+    pub fn lenient(lines: &[String]) -> Self {
+        let width = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for line in lines {
+            cells.extend(line.chars().map(Cell::from_char).chain(iter::repeat(Cell::Open)).take(width));
+        }
+
+        TreeMap { cells, width, height: lines.len() }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The square at `pos`, with the map repeating to the right. `None` past
+    /// the bottom, or for a map with no columns.
+    pub fn get(&self, pos: &Pos) -> Option<Cell> {
+        if pos.y >= self.height || self.width == 0 {
+            return None;
+        }
+        Some(self.cells[pos.y * self.width + pos.x % self.width])
+    }
+
+    /// Trees hit going down from the top left corner along `slope`. A slope
+    /// that doesn't go down never reaches the bottom and counts none.
+    pub fn count_trees(&self, slope: &Slope) -> usize {
+        let Slope(right, down) = *slope;
+        if self.width == 0 || down == 0 {
+            return 0;
+        }
+
+        let right = right % self.width;
+        let mut x = 0;
+        let mut trees = 0;
+        for row in self.cells.chunks_exact(self.width).step_by(down) {
+            trees += usize::from(row[x] == Cell::Tree);
+            x += right;
+            if x >= self.width {
+                x -= self.width;
+            }
+        }
+
+        trees
+    }
+}
+
+impl Display for Slope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "right {}, down {}", self.0, self.1)
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// The slopes [`search_slopes`] tries, going at most `max_right` right and
+/// `max_down` down per step.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SlopeSpace {
+    /// Every pair of steps, so `(2, 2)` is tried as well as `(1, 1)`.
+    Grid { max_right: usize, max_down: usize },
+    /// One slope per direction, in lowest terms: `(1, 1)` but not `(2, 2)`.
+    Rational { max_right: usize, max_down: usize },
+}
+
+impl SlopeSpace {
+    pub fn slopes(&self) -> Vec<Slope> {
+        let (max_right, max_down, reduced) = match *self {
+            SlopeSpace::Grid { max_right, max_down } => (max_right, max_down, false),
+            SlopeSpace::Rational { max_right, max_down } => (max_right, max_down, true),
+        };
+
+        (1..=max_down)
+            .flat_map(|down| (0..=max_right).map(move |right| Slope(right, down)))
+            .filter(|slope| !reduced || gcd(slope.0, slope.1) == 1)
+            .collect()
+    }
+}
+
+/// Every slope tried by [`search_slopes`] with the trees it hits, fewest
+/// first. Slopes with the same count keep the order they were tried in.
+#[derive(Debug, Clone)]
+pub struct SlopeRanking {
+    pub ranked: Vec<(Slope, usize)>,
+}
+
+impl SlopeRanking {
+    /// The slopes hitting the fewest trees, all of them if several tie.
+    pub fn fewest(&self) -> &[(Slope, usize)] {
+        let count = self.ranked.first().map_or(0, |(_, trees)| *trees);
+        let end = self.ranked.iter().position(|(_, trees)| *trees != count).unwrap_or(self.ranked.len());
+        &self.ranked[..end]
+    }
+
+    /// The slopes hitting the most trees, all of them if several tie.
+    pub fn most(&self) -> &[(Slope, usize)] {
+        let count = self.ranked.last().map_or(0, |(_, trees)| *trees);
+        let start = self.ranked.iter().rposition(|(_, trees)| *trees != count).map_or(0, |i| i + 1);
+        &self.ranked[start..]
+    }
+}
+
+/// Counts the trees on every slope of `space`, splitting the slopes between
+/// `workers` threads.
+pub fn search_slopes(map: &TreeMap, space: SlopeSpace, workers: usize) -> SlopeRanking {
+    let slopes = space.slopes();
+    let per_worker = slopes.len().div_ceil(workers.max(1)).max(1);
+
+    let mut ranked = thread::scope(|scope| {
+        let handles = slopes
+            .chunks(per_worker)
+            .map(|chunk| {
+                scope.spawn(move || chunk.iter().map(|slope| (*slope, map.count_trees(slope))).collect::<Vec<_>>())
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("slope search worker panicked"))
+            .collect::<Vec<_>>()
+    });
+
+    ranked.sort_by_key(|(_, trees)| *trees);
+    SlopeRanking { ranked }
+}
+
+macro_rules! slopes {
+    () => (
+        Vec::<Slope>::new()
+    );
+    ($(($x:expr,$y:expr)),+) => {
+        vec![$(Slope($x,$y)),+]
+    };
+}
+
+/// A random map `size` rows high. The trees on each slope are counted from
+/// the grid directly, without going through [`TreeMap`].
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let width = rng.between(5, 40) as usize;
+    let grid = (0..size.max(1))
+        .map(|_| (0..width).map(|_| rng.chance(0.2)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let trees = |right: usize, down: usize| {
+        (0..grid.len())
+            .step_by(down)
+            .enumerate()
+            .filter(|(step, y)| grid[*y][step * right % width])
+            .count()
+    };
+
+    let part1 = trees(3, 1);
+    let part2 = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)]
+        .iter()
+        .map(|&(right, down)| trees(right, down))
+        .product::<usize>();
+
+    let lines = grid
+        .iter()
+        .map(|row| row.iter().map(|tree| if *tree { '#' } else { '.' }).collect())
+        .collect();
+    Generated::new(lines, Some(part1.into()), Some(part2.into()))
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u32 = 3;
+    type Parsed = TreeMap;
+
+    fn parse(lines: &[String]) -> error::Result<TreeMap> {
+        TreeMap::new(lines)
+    }
+
+    fn part1(tree_map: &TreeMap) -> error::Result<Answer> {
+        let slope = Slope(3, 1);
+        Ok(tree_map.count_trees(&slope).into())
+    }
+
+    fn part2(tree_map: &TreeMap) -> error::Result<Answer> {
+        let slopes = slopes![(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+        Ok(slopes.iter().fold(1, |accum, slope| tree_map.count_trees(slope) * accum).into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(rows: &[&str]) -> Vec<String> {
+        rows.iter().map(|row| row.to_string()).collect()
+    }
+
+    fn error(rows: &[&str]) -> Error {
+        TreeMap::new(&lines(rows)).unwrap_err()
+    }
+
+    #[test]
+    fn rejects_an_empty_map() {
+        assert_eq!(error(&[]).message(), "The map has no rows");
+    }
+
+    #[test]
+    fn rejects_empty_rows() {
+        let err = error(&["..#", ""]);
+        assert_eq!((err.message(), err.line()), ("Row is empty", Some(2)));
+        assert_eq!(error(&["", "..#"]).line(), Some(1));
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        let err = error(&["..#", ".#"]);
+        assert_eq!(err.message(), "Row is 2 squares wide, but the first row is 3");
+        assert_eq!((err.line(), err.span()), (Some(2), Some(2..3)));
+
+        let err = error(&["..#", ".#..."]);
+        assert_eq!((err.line(), err.span()), (Some(2), Some(3..5)));
+    }
+
+    #[test]
+    fn rejects_unknown_squares() {
+        let err = error(&["..#", ".x#"]);
+        assert_eq!(err.message(), "Unknown square 'x', expected '.' or '#'");
+        assert_eq!((err.line(), err.span()), (Some(2), Some(1..2)));
+    }
+
+    #[test]
+    fn lenient_maps_keep_the_widest_row() {
+        let map = TreeMap::lenient(&lines(&["", "..#", "#x"]));
+        assert_eq!((map.width(), map.height()), (3, 3));
+        assert_eq!(map.get(&Pos::new(2, 1)), Some(Cell::Tree));
+        assert_eq!(map.get(&Pos::new(1, 2)), Some(Cell::Open));
+        assert_eq!(map.get(&Pos::new(2, 2)), Some(Cell::Open));
+        assert_eq!(map.count_trees(&Slope(2, 1)), 1);
+    }
+
+    #[test]
+    fn grid_tries_every_step_pair() {
+        let slopes = SlopeSpace::Grid { max_right: 2, max_down: 2 }.slopes();
+        let expected = [(0, 1), (1, 1), (2, 1), (0, 2), (1, 2), (2, 2)].map(|(right, down)| Slope(right, down));
+        assert_eq!(slopes, expected);
+    }
+
+    #[test]
+    fn rational_tries_each_direction_once() {
+        let slopes = SlopeSpace::Rational { max_right: 2, max_down: 2 }.slopes();
+        assert_eq!(slopes, [Slope(0, 1), Slope(1, 1), Slope(2, 1), Slope(1, 2)]);
+
+        // Straight down is one direction, however far each step goes.
+        let slopes = SlopeSpace::Rational { max_right: 0, max_down: 4 }.slopes();
+        assert_eq!(slopes, [Slope(0, 1)]);
+        assert!(SlopeSpace::Grid { max_right: 3, max_down: 0 }.slopes().is_empty());
+    }
+
+    fn ranking(trees: &[usize]) -> SlopeRanking {
+        SlopeRanking { ranked: trees.iter().enumerate().map(|(i, trees)| (Slope(i, 1), *trees)).collect() }
+    }
+
+    #[test]
+    fn ranking_keeps_every_tie() {
+        let ranked = ranking(&[2, 2, 3, 5, 5, 5]);
+        assert_eq!(ranked.fewest(), &ranked.ranked[..2]);
+        assert_eq!(ranked.most(), &ranked.ranked[3..]);
+
+        let level = ranking(&[4, 4, 4]);
+        assert_eq!(level.fewest().len(), 3);
+        assert_eq!(level.most().len(), 3);
+
+        let empty = ranking(&[]);
+        assert!(empty.fewest().is_empty());
+        assert!(empty.most().is_empty());
+    }
+}
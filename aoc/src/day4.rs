@@ -0,0 +1,437 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use crate::error::{self, span_of, Error};
+use crate::generate::Generated;
+use crate::rng::Rng;
+use crate::solution::{Answer, Solution};
+
+#[derive(Debug, Copy, Clone)]
+pub enum Unit {
+    Cm,
+    In,
+}
+
+impl FromStr for Unit {
+    type Err = &'static str;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "cm" => Ok(Self::Cm),
+            "in" => Ok(Self::In),
+            _ => Err("Invalid unit"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub enum PassportFieldKey {
+    Byr,
+    Iyr,
+    Eyr,
+    Hgt,
+    Hcl,
+    Ecl,
+    Pid,
+    Cid,
+}
+
+#[derive(Debug, Clone)]
+pub enum Color {
+    Hex(u32),
+    Named(String),
+}
+
+
+#[derive(Debug, Clone)]
+pub enum PassportFieldValue {
+    Num(i32),
+    Measure(usize, Unit),
+    Color(Color),
+    IdNum(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct PassportField {
+    pub key: PassportFieldKey,
+    pub value: Option<PassportFieldValue>,
+    pub str: String,
+}
+
+impl PassportField {
+    pub fn parse_number(key: PassportFieldKey, s: &str) -> Self {
+        Self {
+            key,
+            value: match s.parse::<i32>() {
+                Ok(val) => Some(PassportFieldValue::Num(val)),
+                _ => None
+            },
+            str: s.to_string(),
+        }
+    }
+
+    pub fn parse_measure(key: PassportFieldKey, s: &str) -> Self {
+        Self {
+            key,
+            value: {
+                let split = s.len().saturating_sub(2);
+                let val = s.get(..split).and_then(|v| v.parse::<usize>().ok());
+                let unit = s.get(split..).and_then(|u| u.parse::<Unit>().ok());
+
+                match (val, unit) {
+                    (Some(v), Some(u)) => Some(PassportFieldValue::Measure(v, u)),
+                    _ => None
+                }
+            },
+            str: s.to_string(),
+        }
+    }
+
+    pub fn parse_color(key: PassportFieldKey, s: &str) -> Self {
+        Self {
+            key,
+            value: {
+                if let Some(hex) = s.strip_prefix('#') {
+                    match u32::from_str_radix(hex, 16) {
+                        Ok(hex) => Some(PassportFieldValue::Color(Color::Hex(hex))),
+                        _ => None
+                    }
+                } else {
+                    Some(PassportFieldValue::Color(Color::Named(s.to_string())))
+                }
+            },
+            str: s.to_string(),
+        }
+    }
+
+    pub fn parse_id(key: PassportFieldKey, s: &str) -> Self {
+        Self {
+            key,
+            value: {
+                match s.parse::<u32>() {
+                    Ok(_) => Some(PassportFieldValue::IdNum(s.to_string())),
+                    _ => None,
+                }
+            },
+            str: s.to_string(),
+        }
+    }
+}
+
+impl TryFrom<(&str, &str)> for PassportField {
+    type Error = Error;
+
+    fn try_from(field: (&str, &str)) -> Result<Self, Self::Error> {
+        let err = || Error::new(format!("Unknown passport field: {}", field.0));
+
+        let num = |key| Self::parse_number(key, field.1);
+        let measure = |key| Self::parse_measure(key, field.1);
+        let color = |key| Self::parse_color(key, field.1);
+        let id = |key| Self::parse_id(key, field.1);
+
+        Ok(match field.0 {
+            "byr" => num(PassportFieldKey::Byr),
+            "iyr" => num(PassportFieldKey::Iyr),
+            "eyr" => num(PassportFieldKey::Eyr),
+            "hgt" => measure(PassportFieldKey::Hgt),
+            "hcl" => color(PassportFieldKey::Hcl),
+            "ecl" => color(PassportFieldKey::Ecl),
+            "pid" => id(PassportFieldKey::Pid),
+            "cid" => id(PassportFieldKey::Cid),
+            _ => return Err(err()),
+        })
+    }
+}
+
+
+pub fn parse_passport_fields(line: &str) -> impl Iterator<Item = Result<PassportField, Error>> + '_ {
+    line.split(' ').filter(|x| !x.is_empty()).map(move |x| {
+        let (key, value) = x.split_once(':').ok_or_else(|| {
+            Error::new("Expected `key:value`").with_span(span_of(line, x))
+        })?;
+        PassportField::try_from((key, value)).map_err(|err| err.with_span(span_of(line, key)))
+    })
+}
+
+#[derive(Debug)]
+pub struct Passport {
+    inner: HashMap<PassportFieldKey, PassportField>
+}
+
+impl Default for Passport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Passport {
+    pub fn new() -> Self {
+        Passport {
+            inner: std::collections::HashMap::new()
+        }
+    }
+
+    /// Builds a passport from its lines, each paired with its index in the
+    /// input so errors can point at it.
+    pub fn from_lines(lines: Vec<(usize, &String)>) -> Result<Self, Error> {
+        let mut hashmap = std::collections::HashMap::new();
+
+        for (index, line) in lines {
+            for field in parse_passport_fields(line) {
+                let field = field.map_err(|err| err.with_line(index, line))?;
+                hashmap.insert(field.key, field);
+            }
+        }
+
+        Ok(Passport {
+            inner: hashmap
+        })
+    }
+
+    pub fn has_key(&self, key: PassportFieldKey) -> bool {
+        self.inner.contains_key(&key)
+    }
+
+    pub fn fields(&self) -> impl Iterator<Item = (&PassportFieldKey, &PassportField)> + '_ {
+        self.inner.iter()
+    }
+}
+
+pub fn parse_passport_batch(lines: &[String]) -> Result<Vec<Passport>, Error> {
+    let mut passports = vec![];
+    let mut batched_lines: Vec<(usize, &String)> = Vec::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        if line.is_empty() && !batched_lines.is_empty() {
+            passports.push(Passport::from_lines(batched_lines)?);
+            batched_lines = Vec::new();
+            continue;
+        }
+        batched_lines.push((index, line));
+    }
+
+    if !batched_lines.is_empty() {
+        passports.push(Passport::from_lines(batched_lines)?);
+    }
+
+    Ok(passports)
+}
+
+pub fn has_required_fields(passport: &Passport) -> bool {
+    let required_fields = vec![
+        PassportFieldKey::Byr,
+        PassportFieldKey::Iyr,
+        PassportFieldKey::Eyr,
+        PassportFieldKey::Hgt,
+        PassportFieldKey::Hcl,
+        PassportFieldKey::Ecl,
+        PassportFieldKey::Pid,
+    ];
+
+    for key in required_fields {
+        if !passport.has_key(key) {
+            return false;
+        }
+    }
+
+    true
+}
+
+pub fn is_valid_byr(value: &Option<PassportFieldValue>) -> bool {
+    if let Some(PassportFieldValue::Num(year)) = value {
+        return *year >= 1920 && *year <= 2002;
+    }
+    false
+}
+
+pub fn is_valid_iyr(value: &Option<PassportFieldValue>) -> bool {
+    if let Some(PassportFieldValue::Num(year)) = value {
+        return *year >= 2010 && *year <= 2020;
+    }
+    false
+}
+
+pub fn is_valid_eyr(value: &Option<PassportFieldValue>) -> bool {
+    if let Some(PassportFieldValue::Num(year)) = value {
+        return *year >= 2020 && *year <= 2030;
+    }
+    false
+}
+
+pub fn is_valid_hgt(value: &Option<PassportFieldValue>) -> bool {
+    if let Some(PassportFieldValue::Measure(height, unit)) = value {
+        return match unit {
+            Unit::Cm => *height >= 150 && *height <= 193,
+            Unit::In => *height >= 59 && *height <= 76,
+        }
+    }
+    false
+}
+
+pub fn is_valid_hcl(value: &Option<PassportFieldValue>) -> bool {
+    if let Some(PassportFieldValue::Color(Color::Hex(_))) = value {
+        return true;
+    }
+    false
+}
+
+pub fn is_valid_ecl(value: &Option<PassportFieldValue>) -> bool {
+    if let Some(PassportFieldValue::Color(Color::Named(col))) = value {
+        return matches!(col.as_str(), "amb" | "blu" | "brn" | "gry" | "grn" | "hzl" | "oth");
+    }
+    false
+}
+
+pub fn is_valid_pid(value: &Option<PassportFieldValue>) -> bool {
+    if let Some(PassportFieldValue::IdNum(id)) = value {
+        return id.len() == 9;
+    }
+    false
+}
+
+pub fn is_valid_cid(_value: &Option<PassportFieldValue>) -> bool {
+    true
+}
+
+pub fn is_valid_field(field: &PassportField) -> bool {
+    match field.key {
+        PassportFieldKey::Byr => is_valid_byr(&field.value),
+        PassportFieldKey::Iyr => is_valid_iyr(&field.value),
+        PassportFieldKey::Eyr => is_valid_eyr(&field.value),
+        PassportFieldKey::Hgt => is_valid_hgt(&field.value),
+        PassportFieldKey::Hcl => is_valid_hcl(&field.value),
+        PassportFieldKey::Ecl => is_valid_ecl(&field.value),
+        PassportFieldKey::Pid => is_valid_pid(&field.value),
+        PassportFieldKey::Cid => is_valid_cid(&field.value),
+    }
+}
+
+pub fn is_valid_passport(passport: &Passport) -> bool {
+    if !has_required_fields(passport) {
+        return false;
+    }
+
+    for (_, field) in passport.fields() {
+        if !is_valid_field(field) {
+            return false;
+        }
+    }
+
+    true
+}
+
+fn generate_field(rng: &mut Rng, key: &str, valid: bool) -> String {
+    let digits = |rng: &mut Rng, n: usize| (0..n).map(|_| rng.below(10).to_string()).collect::<String>();
+    let hex = |rng: &mut Rng| (0..6).map(|_| *rng.choose(b"0123456789abcdef") as char).collect::<String>();
+    let outside = |rng: &mut Rng, below: (i64, i64), above: (i64, i64)| {
+        let range = if rng.chance(0.5) { below } else { above };
+        rng.between(range.0, range.1).to_string()
+    };
+
+    let value = match (key, valid) {
+        ("byr", true) => rng.between(1920, 2002).to_string(),
+        ("byr", false) => outside(rng, (1900, 1919), (2003, 2010)),
+        ("iyr", true) => rng.between(2010, 2020).to_string(),
+        ("iyr", false) => outside(rng, (2000, 2009), (2021, 2030)),
+        ("eyr", true) => rng.between(2020, 2030).to_string(),
+        ("eyr", false) => outside(rng, (2010, 2019), (2031, 2040)),
+        ("hgt", true) if rng.chance(0.5) => format!("{}cm", rng.between(150, 193)),
+        ("hgt", true) => format!("{}in", rng.between(59, 76)),
+        ("hgt", false) => match rng.below(3) {
+            0 => format!("{}cm", outside(rng, (100, 149), (194, 220))),
+            1 => format!("{}in", outside(rng, (40, 58), (77, 90))),
+            _ => rng.between(150, 193).to_string(),
+        },
+        ("hcl", true) => format!("#{}", hex(rng)),
+        ("hcl", false) if rng.chance(0.5) => hex(rng),
+        ("hcl", false) => format!("#{}z", &hex(rng)[1..]),
+        ("ecl", true) => rng.choose(&["amb", "blu", "brn", "gry", "grn", "hzl", "oth"]).to_string(),
+        ("ecl", false) => rng.choose(&["zzz", "red", "blk", "wht"]).to_string(),
+        ("pid", true) => digits(rng, 9),
+        ("pid", false) => {
+            let len = *rng.choose(&[8, 10]);
+            digits(rng, len)
+        }
+        (_, _) => rng.between(100, 350).to_string(),
+    };
+
+    format!("{}:{}", key, value)
+}
+
+/// A batch of `size` passports. Each one is valid, is missing a required
+/// field, or has every field but one with a value out of range; a mix of
+/// the last two sets the parts apart.
+pub fn generate(rng: &mut Rng, size: usize) -> Generated {
+    let required = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+    let mut lines = vec![];
+    let (mut complete, mut valid) = (0, 0);
+
+    for index in 0..size {
+        let missing = match rng.below(3) {
+            0 => Some(rng.below(required.len())),
+            _ => None,
+        };
+        let bad = match (missing, rng.chance(0.5)) {
+            (None, true) => Some(rng.below(required.len())),
+            _ => None,
+        };
+
+        let mut fields = required
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| Some(*i) != missing)
+            .map(|(i, key)| generate_field(rng, key, Some(i) != bad))
+            .collect::<Vec<_>>();
+        if rng.chance(0.5) {
+            fields.push(generate_field(rng, "cid", true));
+        }
+        rng.shuffle(&mut fields);
+
+        if missing.is_none() {
+            complete += 1;
+            if bad.is_none() {
+                valid += 1;
+            }
+        }
+
+        if index > 0 {
+            lines.push(String::new());
+        }
+        let mut line = vec![];
+        for field in fields {
+            line.push(field);
+            if rng.chance(0.3) {
+                lines.push(line.join(" "));
+                line.clear();
+            }
+        }
+        if !line.is_empty() {
+            lines.push(line.join(" "));
+        }
+    }
+
+    Generated::new(lines, Some(complete.into()), Some(valid.into()))
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u32 = 4;
+    type Parsed = Vec<Passport>;
+
+    fn parse(lines: &[String]) -> error::Result<Vec<Passport>> {
+        parse_passport_batch(lines)
+    }
+
+    fn part1(passports: &Vec<Passport>) -> error::Result<Answer> {
+        Ok(passports.iter().filter(|x| has_required_fields(x)).count().into())
+    }
+
+    fn part2(passports: &Vec<Passport>) -> error::Result<Answer> {
+        Ok(passports.iter().filter(|x| is_valid_passport(x)).count().into())
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<Generated> {
+        Some(generate(rng, size))
+    }
+}
@@ -0,0 +1,198 @@
+//! Differential testing: a day can register extra implementations of a part,
+//! usually slow but obviously correct ones, and they are run against the
+//! day's [`Solution`](crate::Solution) on the same input. When two disagree
+//! the input is shrunk to the smallest one that still shows the difference.
+
+use std::any::Any;
+use std::fmt::{self, Display};
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::error::Result;
+use crate::solution::{Answer, Solver};
+
+/// Another way of solving one part of a day, from the raw input lines.
+pub struct Implementation {
+    pub name: &'static str,
+    pub part: u8,
+    pub solve: fn(&[String]) -> Result<Answer>,
+}
+
+/// Name the day's own solution goes by in reports.
+pub const SOLUTION: &str = "solution";
+
+/// What an implementation made of an input. Errors and panics both count as
+/// failing, so two implementations that reject the same input agree.
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    Answer(Answer),
+    Failed(String),
+}
+
+impl Outcome {
+    fn of<F: FnOnce() -> Result<Answer>>(solve: F) -> Self {
+        match panic::catch_unwind(AssertUnwindSafe(solve)) {
+            Ok(Ok(answer)) => Outcome::Answer(answer),
+            Ok(Err(err)) => Outcome::Failed(err.to_string()),
+            Err(payload) => Outcome::Failed(format!("panicked: {}", panic_message(payload.as_ref()))),
+        }
+    }
+
+    pub fn agrees(&self, other: &Outcome) -> bool {
+        match (self, other) {
+            (Outcome::Answer(a), Outcome::Answer(b)) => a == b,
+            (Outcome::Failed(_), Outcome::Failed(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Answer(answer) => write!(f, "{}", answer),
+            Outcome::Failed(message) => write!(f, "no answer ({})", message),
+        }
+    }
+}
+
+/// The message a panic was raised with, if it was a string.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_default()
+}
+
+/// Two implementations giving different outcomes for the same input.
+#[derive(Debug, Clone)]
+pub struct Disagreement {
+    pub part: u8,
+    pub lines: Vec<String>,
+    pub expected: Outcome,
+    pub name: &'static str,
+    pub got: Outcome,
+}
+
+impl Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "part{}: {} gave {}, {} gave {}",
+            self.part, SOLUTION, self.expected, self.name, self.got
+        )
+    }
+}
+
+fn solve(solver: &dyn Solver, lines: &[String], part: u8) -> Outcome {
+    Outcome::of(|| {
+        let parsed = solver.parse(lines)?;
+        match part {
+            1 => solver.part1(parsed.as_ref()),
+            _ => solver.part2(parsed.as_ref()),
+        }
+    })
+}
+
+fn disagreement(solver: &dyn Solver, lines: &[String], implementation: &Implementation) -> Option<Disagreement> {
+    let expected = solve(solver, lines, implementation.part);
+    let got = Outcome::of(|| (implementation.solve)(lines));
+
+    if expected.agrees(&got) {
+        return None;
+    }
+    Some(Disagreement {
+        part: implementation.part,
+        lines: lines.to_vec(),
+        expected,
+        name: implementation.name,
+        got,
+    })
+}
+
+/// Runs every registered implementation of `solver` on `lines` and returns
+/// the first one that disagrees with the solution.
+pub fn compare(solver: &dyn Solver, lines: &[String]) -> Option<Disagreement> {
+    solver
+        .implementations()
+        .iter()
+        .find_map(|implementation| disagreement(solver, lines, implementation))
+}
+
+/// Shrinks the input of `found` while the same implementation still
+/// disagrees: first whole runs of lines are dropped, halving the run length
+/// down to single lines, then single characters within each line.
+pub fn shrink(solver: &dyn Solver, found: Disagreement) -> Disagreement {
+    let implementation = match solver.implementations().iter().find(|i| i.name == found.name && i.part == found.part) {
+        Some(implementation) => implementation,
+        None => return found,
+    };
+    let retry = |lines: &[String]| disagreement(solver, lines, implementation);
+
+    let mut best = found;
+    loop {
+        let mut progress = false;
+
+        let mut run = best.lines.len() / 2;
+        while run > 0 {
+            let mut start = 0;
+            while start < best.lines.len() {
+                let mut lines = best.lines.clone();
+                lines.drain(start..(start + run).min(lines.len()));
+                match retry(&lines) {
+                    Some(smaller) => {
+                        best = smaller;
+                        progress = true;
+                    }
+                    None => start += run,
+                }
+            }
+            run /= 2;
+        }
+
+        for index in 0..best.lines.len() {
+            let mut column = 0;
+            while column < best.lines[index].chars().count() {
+                let mut lines = best.lines.clone();
+                lines[index] = lines[index]
+                    .chars()
+                    .enumerate()
+                    .filter(|(i, _)| *i != column)
+                    .map(|(_, c)| c)
+                    .collect();
+                match retry(&lines) {
+                    Some(smaller) => {
+                        best = smaller;
+                        progress = true;
+                    }
+                    None => column += 1,
+                }
+            }
+        }
+
+        if !progress {
+            return best;
+        }
+    }
+}
+
+type PanicHook = Box<dyn Fn(&panic::PanicHookInfo<'_>) + Sync + Send + 'static>;
+
+/// Puts a panic hook back when dropped, even if `f` in [`quietly`] unwinds.
+struct RestoreHook(Option<PanicHook>);
+
+impl Drop for RestoreHook {
+    fn drop(&mut self) {
+        if let Some(hook) = self.0.take() {
+            panic::set_hook(hook);
+        }
+    }
+}
+
+/// Runs `f` with the panic message printer switched off, since the
+/// implementations under test are expected to panic on some inputs.
+pub fn quietly<T, F: FnOnce() -> T>(f: F) -> T {
+    let _restore = RestoreHook(Some(panic::take_hook()));
+    panic::set_hook(Box::new(|_| {}));
+    f()
+}
@@ -0,0 +1,215 @@
+//! The error type shared by every solver.
+//!
+//! Parse errors point at the input they came from: the file, the line and a
+//! span of columns on that line. Rendering one gives a snippet of the input
+//! with the span underlined:
+//!
+//! ```text
+//! Invalid tile: x
+//!  --> day24.txt:3:5
+//!   |
+//! 3 | sesexw
+//!   |     ^
+//! ```
+
+use std::error;
+use std::fmt::{self, Display};
+use std::io;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+type Cause = Box<dyn error::Error + Send + Sync + 'static>;
+
+#[derive(Debug)]
+pub struct Error {
+    message: String,
+    file: Option<PathBuf>,
+    /// 1-based line number and the text of that line, if known.
+    line: Option<(usize, Option<String>)>,
+    /// 0-based, end-exclusive character columns on `line`.
+    span: Option<Range<usize>>,
+    cause: Option<Cause>,
+}
+
+impl Error {
+    pub fn new<S: Into<String>>(message: S) -> Self {
+        Error {
+            message: message.into(),
+            file: None,
+            line: None,
+            span: None,
+            cause: None,
+        }
+    }
+
+    /// An error on the `index`th (0-based) line of the input, `text`.
+    pub fn at_line<S: Into<String>>(message: S, index: usize, text: &str) -> Self {
+        Error::new(message).with_line(index, text)
+    }
+
+    /// Sets the line the error is on, unless it is already known.
+    pub fn with_line(mut self, index: usize, text: &str) -> Self {
+        if self.line.is_none() {
+            self.line = Some((index + 1, Some(text.to_string())));
+        }
+        self
+    }
+
+    /// Sets the `index`th (0-based) line as the one the error is on, unless
+    /// it is already known, leaving its text for [`Error::quoting`] to fill
+    /// in.
+    pub fn on_line(mut self, index: usize) -> Self {
+        if self.line.is_none() {
+            self.line = Some((index + 1, None));
+        }
+        self
+    }
+
+    /// Fills in the text of the line the error is on from `lines`, if only
+    /// its number is known.
+    pub fn quoting(mut self, lines: &[String]) -> Self {
+        if let Some((line, text @ None)) = &mut self.line {
+            *text = lines.get(*line - 1).cloned();
+        }
+        self
+    }
+
+    /// Sets the columns the error covers, unless they are already known.
+    pub fn with_span(mut self, span: Range<usize>) -> Self {
+        if self.span.is_none() {
+            self.span = Some(span);
+        }
+        self
+    }
+
+    /// Moves the span right by `offset` columns, for errors raised on a
+    /// slice of a line.
+    pub fn offset(mut self, offset: usize) -> Self {
+        if let Some(span) = self.span.take() {
+            self.span = Some(span.start + offset..span.end + offset);
+        }
+        self
+    }
+
+    /// Sets the file the error is in, unless it is already known.
+    pub fn in_file<P: AsRef<Path>>(mut self, file: P) -> Self {
+        if self.file.is_none() {
+            self.file = Some(file.as_ref().to_path_buf());
+        }
+        self
+    }
+
+    pub fn caused_by<E>(mut self, cause: E) -> Self
+    where E: Into<Cause>, {
+        self.cause = Some(cause.into());
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    /// 1-based line number.
+    pub fn line(&self) -> Option<usize> {
+        self.line.as_ref().map(|(line, _)| *line)
+    }
+
+    pub fn span(&self) -> Option<Range<usize>> {
+        self.span.clone()
+    }
+
+    fn render_location(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (line, text) = match &self.line {
+            Some((line, text)) => (line, text),
+            None => {
+                if let Some(file) = &self.file {
+                    write!(f, "\n --> {}", file.display())?;
+                }
+                return Ok(());
+            }
+        };
+
+        let file = self
+            .file
+            .as_ref()
+            .map(|file| file.display().to_string())
+            .unwrap_or_else(|| "<input>".to_string());
+        let width = line.to_string().len();
+        let gutter = " ".repeat(width);
+        let column = self.span.as_ref().map(|span| span.start + 1).unwrap_or(1);
+
+        write!(f, "\n{}--> {}:{}:{}", gutter, file, line, column)?;
+        let text = match text {
+            Some(text) => text,
+            None => return Ok(()),
+        };
+        write!(f, "\n{} |", gutter)?;
+        write!(f, "\n{} | {}", line, text)?;
+
+        if let Some(span) = &self.span {
+            let len = text.chars().count();
+            let start = span.start.min(len);
+            let carets = span.end.min(len).saturating_sub(start).max(1);
+            write!(f, "\n{} | {}{}", gutter, " ".repeat(start), "^".repeat(carets))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The character columns `part` covers in `line`. `part` must be a slice of
+/// `line`, as handed out by `split` and friends.
+pub fn span_of(line: &str, part: &str) -> Range<usize> {
+    let offset = (part.as_ptr() as usize).saturating_sub(line.as_ptr() as usize).min(line.len());
+    let start = line[..offset].chars().count();
+    start..start + part.chars().count()
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        self.render_location(f)?;
+
+        let mut cause = self.cause.as_deref().map(|cause| cause as &(dyn error::Error + 'static));
+        while let Some(err) = cause {
+            write!(f, "\ncaused by: {}", err)?;
+            if err.is::<Error>() {
+                // It has already rendered the rest of the chain.
+                break;
+            }
+            cause = err.source();
+        }
+
+        Ok(())
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        self.cause.as_deref().map(|cause| cause as &(dyn error::Error + 'static))
+    }
+}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Error::new(message)
+    }
+}
+
+impl From<&str> for Error {
+    fn from(message: &str) -> Self {
+        Error::new(message)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::new("I/O error").caused_by(err)
+    }
+}
@@ -0,0 +1,64 @@
+//! Example inputs from the puzzle text, `dayN-test*.txt`, with their expected
+//! answers in a sidecar answers file (`day2-test.txt` -> `day2-test.answers`).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::answers::{self, Verdict};
+use crate::error::{Error, Result};
+use crate::runner;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    pub day: u32,
+    pub path: PathBuf,
+}
+
+impl Fixture {
+    /// The file name without its extension, e.g. `day2-test`.
+    pub fn name(&self) -> String {
+        self.path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    /// Runs the day's solver on the fixture and checks every part the
+    /// sidecar has an answer for.
+    pub fn check(&self) -> Result<Vec<(u8, Verdict)>> {
+        let sidecar = answers::answers_path(&self.path);
+        if !sidecar.is_file() {
+            return Err(Error::new(format!("No answers for {}, expected them in {}", self.path.display(), sidecar.display())));
+        }
+
+        let solver = runner::find_solver(self.day)?;
+        answers::verify(solver, &self.path)
+    }
+}
+
+/// The day a fixture file belongs to: `day24-test.txt` and
+/// `day24-test2.txt` are both day 24.
+pub fn fixture_day(file_name: &str) -> Option<u32> {
+    let rest = file_name.strip_prefix("day")?.strip_suffix(".txt")?;
+    let (day, _) = rest.split_once("-test")?;
+    day.parse().ok()
+}
+
+/// Every fixture in `dir`, ordered by day and then name.
+pub fn discover(dir: &Path) -> Result<Vec<Fixture>> {
+    let entries = fs::read_dir(dir)
+        .map_err(|err| Error::new(format!("Failed to list {}", dir.display())).caused_by(err))?;
+
+    let mut fixtures = vec![];
+    for entry in entries {
+        let entry = entry.map_err(|err| Error::new(format!("Failed to list {}", dir.display())).caused_by(err))?;
+        let path = entry.path();
+        let day = path.file_name().and_then(|name| name.to_str()).and_then(fixture_day);
+        if let (Some(day), true) = (day, path.is_file()) {
+            fixtures.push(Fixture { day, path });
+        }
+    }
+
+    fixtures.sort_by_key(|fixture| (fixture.day, fixture.name()));
+    Ok(fixtures)
+}
@@ -0,0 +1,83 @@
+//! Random puzzle inputs with their answers known up front, for stress and
+//! fuzz testing the solvers without real puzzle inputs.
+
+use std::fs;
+use std::path::Path;
+
+use crate::answers::{Answers, Expected, Verdict};
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::rng::Rng;
+use crate::runner;
+use crate::solution::{Answer, Solver};
+
+/// A generated input and the answers planted in it. A part is `None` when
+/// the generator can't vouch for its answer.
+#[derive(Debug, Clone)]
+pub struct Generated {
+    pub input: String,
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Generated {
+    pub fn new(lines: Vec<String>, part1: Option<Answer>, part2: Option<Answer>) -> Self {
+        let mut input = lines.join("\n");
+        input.push('\n');
+        Generated { input, part1, part2 }
+    }
+
+    pub fn answer(&self, part: u8) -> Option<&Answer> {
+        match part {
+            1 => self.part1.as_ref(),
+            2 => self.part2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Writes the input to `path` and the known answers, in plain text, to
+    /// its answers file so `aoc verify --input` can check it later.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, &self.input)
+            .map_err(|err| Error::new(format!("Failed to write {}", path.display())).caused_by(err))?;
+
+        let mut answers = Answers::default();
+        for part in 1..=2 {
+            if let Some(answer) = self.answer(part) {
+                answers.set(part, Expected::plain(answer));
+            }
+        }
+        answers.save(path)
+    }
+}
+
+/// Generates an input for `solver` from `seed`, or `None` if the day has no
+/// generator.
+pub fn generate(solver: &dyn Solver, seed: u64, size: usize) -> Option<Generated> {
+    solver.generate(&mut Rng::new(seed), size)
+}
+
+/// Solves a generated input and checks it against the planted answers.
+pub fn check(solver: &dyn Solver, name: &str, generated: &Generated) -> Vec<(u8, Verdict)> {
+    let input = Input::from_text(name, &generated.input);
+    let parsed = match runner::parse_input(solver, &input) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            let verdict = Verdict::Error(err.to_string());
+            return runner::parts(solver, None).into_iter().map(|part| (part, verdict.clone())).collect();
+        }
+    };
+
+    runner::parts(solver, None)
+        .into_iter()
+        .map(|part| {
+            let verdict = match (runner::solve_part(solver, parsed.as_ref(), part), generated.answer(part)) {
+                (Err(err), _) => Verdict::Error(input.locate(err).to_string()),
+                (Ok(answer), None) => Verdict::Unknown(answer),
+                (Ok(answer), Some(expected)) if answer == *expected => Verdict::Pass,
+                (Ok(answer), Some(_)) => Verdict::Fail(answer),
+            };
+            (part, verdict)
+        })
+        .collect()
+}
@@ -0,0 +1,199 @@
+//! Where puzzle input comes from: a file, stdin, a string in memory, or an
+//! input compiled into the binary with the `embedded-inputs` feature.
+
+use std::fmt::{self, Display};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Path(PathBuf),
+    Stdin,
+    Text { name: String, text: String },
+    Embedded(u32),
+}
+
+impl Source {
+    /// The source for a command line argument: `-` is stdin, anything else a
+    /// path.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::Path(PathBuf::from(path)),
+        }
+    }
+
+    pub fn text<N: Into<String>, T: Into<String>>(name: N, text: T) -> Self {
+        Source::Text { name: name.into(), text: text.into() }
+    }
+
+    pub fn load(&self) -> Result<Input> {
+        match self {
+            Source::Path(path) => Input::from_path(path),
+            Source::Stdin => Input::from_stdin(),
+            Source::Text { name, text } => Ok(Input::from_text(name, text)),
+            Source::Embedded(day) => Input::embedded(*day),
+        }
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Path(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "<stdin>"),
+            Source::Text { name, .. } => write!(f, "{}", name),
+            Source::Embedded(day) => write!(f, "<embedded day{}.txt>", day),
+        }
+    }
+}
+
+/// A loaded puzzle input, split into lines.
+#[derive(Debug, Clone)]
+pub struct Input {
+    source: Source,
+    lines: Vec<String>,
+}
+
+impl Input {
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = fs::read(path).map_err(|err| match err.kind() {
+            io::ErrorKind::NotFound => Error::new(format!("Input file not found: {}", path.display())),
+            _ => Error::new(format!("Failed to read {}", path.display())).caused_by(err),
+        })?;
+
+        Input::from_bytes(Source::Path(path.to_path_buf()), bytes)
+    }
+
+    pub fn from_stdin() -> Result<Self> {
+        let mut bytes = vec![];
+        io::stdin()
+            .lock()
+            .read_to_end(&mut bytes)
+            .map_err(|err| Error::new("Failed to read stdin").caused_by(err))?;
+
+        Input::from_bytes(Source::Stdin, bytes)
+    }
+
+    /// An input held in memory, e.g. an example in a test. `name` stands in
+    /// for the file name in errors.
+    pub fn from_text(name: &str, text: &str) -> Self {
+        Input {
+            source: Source::text(name, text),
+            lines: text.lines().map(|line| line.to_string()).collect(),
+        }
+    }
+
+    /// The input for `day` compiled into the binary.
+    pub fn embedded(day: u32) -> Result<Self> {
+        let text = embedded::text(day).ok_or_else(|| {
+            if embedded::ENABLED {
+                Error::new(format!("No embedded input for day {}", day))
+            } else {
+                Error::new("Embedded inputs are not available; build with `--features embedded-inputs`")
+            }
+        })?;
+
+        Ok(Input {
+            source: Source::Embedded(day),
+            lines: text.lines().map(|line| line.to_string()).collect(),
+        })
+    }
+
+    fn from_bytes(source: Source, bytes: Vec<u8>) -> Result<Self> {
+        let text = String::from_utf8(bytes).map_err(|err| {
+            let bytes = err.as_bytes();
+            let valid = err.utf8_error().valid_up_to();
+            let index = bytes[..valid].iter().filter(|b| **b == b'\n').count();
+            let start = bytes[..valid].iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+            let end = bytes[start..].iter().position(|b| *b == b'\n').map_or(bytes.len(), |i| start + i);
+            let line = String::from_utf8_lossy(&bytes[start..end]);
+            let column = String::from_utf8_lossy(&bytes[start..valid]).chars().count();
+
+            Error::at_line("Input is not valid UTF-8", index, &line)
+                .with_span(column..column + 1)
+                .in_file(source.to_string())
+        })?;
+
+        Ok(Input {
+            lines: text.lines().map(|line| line.to_string()).collect(),
+            source,
+        })
+    }
+
+    pub fn source(&self) -> &Source {
+        &self.source
+    }
+
+    /// Name of the input for messages: its path, `<stdin>`, and so on.
+    pub fn name(&self) -> String {
+        self.source.to_string()
+    }
+
+    /// Points `err` at this input: its name, and the text of the line the
+    /// error is on when only the line number is known.
+    pub fn locate(&self, err: Error) -> Error {
+        err.in_file(self.name()).quoting(&self.lines)
+    }
+
+    pub fn lines(&self) -> &[String] {
+        &self.lines
+    }
+}
+
+/// Input files tried for a day when none is given, in order of preference.
+pub fn input_candidates(day: u32) -> [PathBuf; 2] {
+    [
+        PathBuf::from(format!("day{}.txt", day)),
+        PathBuf::from(format!("day{}-test.txt", day)),
+    ]
+}
+
+/// Finds the input for `day` by convention: `dayN.txt` or `dayN-test.txt` in
+/// the current directory, or the embedded input when there is one.
+pub fn find_input(day: u32) -> Result<Source> {
+    let candidates = input_candidates(day);
+    if let Some(path) = candidates.iter().find(|path| path.is_file()) {
+        return Ok(Source::Path(path.clone()));
+    }
+    if embedded::text(day).is_some() {
+        return Ok(Source::Embedded(day));
+    }
+
+    let names = candidates
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>();
+    Err(Error::new(format!("No input found for day {} (looked for {})", day, names.join(", "))))
+}
+
+#[cfg(feature = "embedded-inputs")]
+mod embedded {
+    pub const ENABLED: bool = true;
+
+    pub fn text(day: u32) -> Option<&'static str> {
+        match day {
+            1 => Some(include_str!("../../day1.txt")),
+            2 => Some(include_str!("../../day2.txt")),
+            3 => Some(include_str!("../../day3.txt")),
+            4 => Some(include_str!("../../day4.txt")),
+            23 => Some(include_str!("../../day23.txt")),
+            24 => Some(include_str!("../../day24.txt")),
+            25 => Some(include_str!("../../day25.txt")),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(not(feature = "embedded-inputs"))]
+mod embedded {
+    pub const ENABLED: bool = false;
+
+    pub fn text(_day: u32) -> Option<&'static str> {
+        None
+    }
+}
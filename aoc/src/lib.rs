@@ -0,0 +1,39 @@
+pub mod answers;
+pub mod args;
+pub mod bench;
+pub mod diff;
+pub mod error;
+pub mod fixtures;
+pub mod generate;
+pub mod input;
+pub mod json;
+pub mod rng;
+pub mod runner;
+pub mod sha256;
+pub mod solution;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+pub use error::Error;
+pub use solution::{Answer, Solution, Solver};
+
+/// Every implemented day, in order.
+pub static SOLVERS: &[&dyn Solver] = &[
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day23::Day23,
+    &day24::Day24,
+    &day25::Day25,
+];
+
+pub fn solver(day: u32) -> Option<&'static dyn Solver> {
+    SOLVERS.iter().copied().find(|solver| solver.day() == day)
+}
@@ -0,0 +1,365 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+use aoc::error::{Error, Result};
+use aoc::answers;
+use aoc::args::Args;
+use aoc::bench::{self, BenchOptions};
+use aoc::diff;
+use aoc::generate;
+use aoc::input::{self, Source};
+use aoc::rng::Rng;
+use aoc::runner;
+
+const USAGE: &str = "\
+Usage: aoc <command> [options]
+
+Commands:
+    run <day> [--part 1|2] [--input PATH]
+        Solve a day's puzzle. The input defaults to dayN.txt, falling back to
+        dayN-test.txt, in the current directory. Use `--input -` for stdin.
+
+    verify [day] [--input PATH] [--record] [--plain]
+        Check every day's answers, or just `day`'s, against the dayN.answers
+        and dayN-test.answers files. `--record` writes the current answers
+        instead, hashed with the key in AOC_ANSWER_KEY or .answer-key unless
+        `--plain` is given. Hashed answers are unknown without the key.
+
+    bench [day] [--input PATH] [--iterations N] [--warmup N] [--json PATH]
+        Time the parse, part1 and part2 phases of every day, or just `day`,
+        and report the min, median and p95. `--json` also writes the results
+        as JSON, e.g. `--json bench_output.txt`.
+
+    gen <day> [--seed N] [--size N] [--output PATH]
+        Print a random input for `day` with about `size` entries (default
+        100). The seed and the answers planted in the input go to stderr.
+        `--output` writes the input to PATH, and its answers next to it.
+
+    fuzz [day] [--seed N] [--runs N] [--size N]
+        Solve `runs` generated inputs (default 100) for every day, or just
+        `day`, and report any answer that differs from the planted one with
+        the seed that reproduces it.
+
+    diff [day] [--input PATH] [--seed N] [--runs N] [--size N]
+        Run every other implementation a day registers against its solution,
+        on the day's real input and on `runs` generated inputs (default 100),
+        or only on PATH if given. Disagreements are shrunk to the smallest
+        input that still shows them.";
+
+fn parse_day(arg: Option<String>) -> Result<u32> {
+    let arg = arg.ok_or("Missing day")?;
+    arg.trim_start_matches("day")
+        .parse::<u32>()
+        .map_err(|_| Error::new(format!("Invalid day: {}", arg)))
+}
+
+fn parse_part(arg: Option<String>) -> Result<Option<u8>> {
+    match arg.as_deref() {
+        None => Ok(None),
+        Some("1") => Ok(Some(1)),
+        Some("2") => Ok(Some(2)),
+        Some(arg) => Err(Error::new(format!("Invalid part: {} (expected 1 or 2)", arg))),
+    }
+}
+
+fn cmd_run(mut args: Args) -> Result<()> {
+    let part = parse_part(args.option("part")?)?;
+    let source = args.option("input")?.map(|arg| Source::from_arg(&arg));
+    let day = parse_day(args.positional())?;
+    args.finish()?;
+
+    let solver = runner::find_solver(day)?;
+    let source = match source {
+        Some(source) => source,
+        None => input::find_input(day)?,
+    };
+
+    let input = source.load()?;
+    let parsed = runner::parse_input(solver, &input)?;
+
+    for part in runner::parts(solver, part) {
+        let answer = runner::solve_part(solver, parsed.as_ref(), part).map_err(|err| input.locate(err))?;
+        println!("Part{}: {}", part, answer);
+    }
+
+    Ok(())
+}
+
+fn cmd_verify(mut args: Args) -> Result<()> {
+    let record = args.flag("record");
+    let plain = args.flag("plain");
+    let input = args.option("input")?.map(PathBuf::from);
+    let day = args.positional().map(|day| parse_day(Some(day))).transpose()?;
+    args.finish()?;
+
+    let solvers = match day {
+        Some(day) => vec![runner::find_solver(day)?],
+        None if input.is_some() => return Err(Error::new("--input needs a day")),
+        None => aoc::SOLVERS.to_vec(),
+    };
+
+    let mut failures = 0;
+    for solver in solvers {
+        let inputs = match &input {
+            Some(input) => vec![input.clone()],
+            None => answers::inputs(solver.day())?,
+        };
+        if inputs.is_empty() && day.is_some() {
+            input::find_input(solver.day())?;
+        }
+
+        for input in inputs {
+            if record {
+                let path = answers::answers_path(&input);
+                answers::record(solver, &input, plain)?;
+                println!("{}: recorded {}", input.display(), path.display());
+                continue;
+            }
+
+            for (part, verdict) in answers::verify(solver, &input)? {
+                println!("{}: part{}: {}", input.display(), part, verdict);
+                failures += verdict.is_failure() as usize;
+            }
+        }
+    }
+
+    match failures {
+        0 => Ok(()),
+        1 => Err(Error::new("1 answer failed verification")),
+        n => Err(Error::new(format!("{} answers failed verification", n))),
+    }
+}
+
+fn cmd_bench(mut args: Args) -> Result<()> {
+    let defaults = BenchOptions::default();
+    let options = BenchOptions {
+        iterations: args.parse_or("iterations", defaults.iterations)?,
+        warmup: args.parse_or("warmup", defaults.warmup)?,
+    };
+    if options.iterations == 0 {
+        return Err(Error::new("--iterations must be at least 1"));
+    }
+    let json = args.option("json")?.map(PathBuf::from);
+    let input = args.option("input")?.map(|arg| Source::from_arg(&arg));
+    let day = args.positional().map(|day| parse_day(Some(day))).transpose()?;
+    args.finish()?;
+
+    let solvers = match day {
+        Some(day) => vec![runner::find_solver(day)?],
+        None if input.is_some() => return Err(Error::new("--input needs a day")),
+        None => aoc::SOLVERS.to_vec(),
+    };
+
+    println!("{:<5} {:<14} {:<6} {:>12} {:>12} {:>12}", "day", "input", "phase", "min", "median", "p95");
+
+    let mut reports = vec![];
+    for solver in solvers {
+        let source = match &input {
+            Some(source) => source.clone(),
+            None => input::find_input(solver.day())?,
+        };
+        let report = bench::bench(solver, &source.load()?, &options)?;
+
+        for (phase, stats) in report.phases.iter() {
+            println!(
+                "{:<5} {:<14} {:<6} {:>12.2?} {:>12.2?} {:>12.2?}",
+                report.day,
+                report.input,
+                phase,
+                stats.min,
+                stats.median,
+                stats.p95,
+            );
+        }
+        reports.push(report);
+    }
+
+    if let Some(path) = json {
+        fs::write(&path, bench::to_json(&reports, &options))
+            .map_err(|err| Error::new(format!("Failed to write {}", path.display())).caused_by(err))?;
+    }
+
+    Ok(())
+}
+
+fn parse_seed(arg: Option<String>) -> Result<u64> {
+    match arg {
+        Some(arg) => arg.parse::<u64>().map_err(|_| Error::new(format!("Invalid value for --seed: {}", arg))),
+        None => Ok(Rng::time_seed()),
+    }
+}
+
+fn cmd_gen(mut args: Args) -> Result<()> {
+    let seed = parse_seed(args.option("seed")?)?;
+    let size = args.parse_or("size", 100)?;
+    let output = args.option("output")?.map(PathBuf::from);
+    let day = parse_day(args.positional())?;
+    args.finish()?;
+
+    let solver = runner::find_solver(day)?;
+    let generated = generate::generate(solver, seed, size)
+        .ok_or_else(|| Error::new(format!("Day {} has no generator", day)))?;
+
+    eprintln!("seed: {}", seed);
+    for part in runner::parts(solver, None) {
+        match generated.answer(part) {
+            Some(answer) => eprintln!("Part{}: {}", part, answer),
+            None => eprintln!("Part{}: unknown", part),
+        }
+    }
+
+    match output {
+        Some(path) => generated.save(&path),
+        None => {
+            print!("{}", generated.input);
+            Ok(())
+        }
+    }
+}
+
+fn cmd_fuzz(mut args: Args) -> Result<()> {
+    let seed = parse_seed(args.option("seed")?)?;
+    let runs = args.parse_or("runs", 100)?;
+    let size = args.parse_or("size", 100)?;
+    let day = args.positional().map(|day| parse_day(Some(day))).transpose()?;
+    args.finish()?;
+
+    let solvers = match day {
+        Some(day) => vec![runner::find_solver(day)?],
+        None => aoc::SOLVERS.to_vec(),
+    };
+
+    println!("seed: {}", seed);
+
+    let mut failures = 0;
+    for solver in solvers {
+        let mut failed = 0;
+        let mut generated_any = false;
+        for run in 0..runs {
+            let seed = seed.wrapping_add(run as u64);
+            let generated = match generate::generate(solver, seed, size) {
+                Some(generated) => generated,
+                None => break,
+            };
+            generated_any = true;
+
+            let name = format!("<day{} seed {}>", solver.day(), seed);
+            for (part, verdict) in generate::check(solver, &name, &generated) {
+                if verdict.is_failure() {
+                    let expected = generated.answer(part).map(|answer| answer.to_string()).unwrap_or_default();
+                    println!("day{}: seed {}: part{}: {}, expected {}", solver.day(), seed, part, verdict, expected);
+                    failed += 1;
+                }
+            }
+        }
+
+        if generated_any {
+            println!("day{}: {} runs, {} failed", solver.day(), runs, failed);
+        } else {
+            println!("day{}: no generator", solver.day());
+        }
+        failures += failed;
+    }
+
+    match failures {
+        0 => Ok(()),
+        1 => Err(Error::new("1 answer differed from the generated one")),
+        n => Err(Error::new(format!("{} answers differed from the generated ones", n))),
+    }
+}
+
+fn cmd_diff(mut args: Args) -> Result<()> {
+    let seed = parse_seed(args.option("seed")?)?;
+    let runs = args.parse_or("runs", 100)?;
+    let size = args.parse_or("size", 100)?;
+    let input = args.option("input")?.map(|arg| Source::from_arg(&arg));
+    let day = args.positional().map(|day| parse_day(Some(day))).transpose()?;
+    args.finish()?;
+
+    let solvers = match day {
+        Some(day) => vec![runner::find_solver(day)?],
+        None if input.is_some() => return Err(Error::new("--input needs a day")),
+        None => aoc::SOLVERS.to_vec(),
+    };
+
+    println!("seed: {}", seed);
+
+    let mut failures = 0;
+    for solver in solvers {
+        let mut names: Vec<&str> = vec![];
+        for implementation in solver.implementations() {
+            if !names.contains(&implementation.name) {
+                names.push(implementation.name);
+            }
+        }
+        if names.is_empty() {
+            println!("day{}: no other implementations", solver.day());
+            continue;
+        }
+
+        let mut inputs = vec![];
+        match &input {
+            Some(source) => inputs.push((source.to_string(), source.load()?.lines().to_vec())),
+            None => {
+                if let Ok(source) = input::find_input(solver.day()) {
+                    inputs.push((source.to_string(), source.load()?.lines().to_vec()));
+                }
+                for run in 0..runs {
+                    let seed = seed.wrapping_add(run as u64);
+                    if let Some(generated) = generate::generate(solver, seed, size) {
+                        let lines = generated.input.lines().map(|line| line.to_string()).collect();
+                        inputs.push((format!("seed {}", seed), lines));
+                    }
+                }
+            }
+        }
+
+        let found = diff::quietly(|| {
+            inputs.iter().find_map(|(name, lines)| {
+                diff::compare(solver, lines).map(|found| (name, diff::shrink(solver, found)))
+            })
+        });
+
+        match found {
+            None => println!("day{}: {} agrees with {} on {} inputs", solver.day(), diff::SOLUTION, names.join(", "), inputs.len()),
+            Some((name, found)) => {
+                failures += 1;
+                println!("day{}: {}: {}", solver.day(), name, found);
+                println!("smallest input that disagrees ({} lines):", found.lines.len());
+                for line in found.lines.iter() {
+                    println!("    {}", line);
+                }
+            }
+        }
+    }
+
+    match failures {
+        0 => Ok(()),
+        1 => Err(Error::new("1 day has implementations that disagree")),
+        n => Err(Error::new(format!("{} days have implementations that disagree", n))),
+    }
+}
+
+fn run(mut args: Args) -> Result<()> {
+    match args.command().as_deref() {
+        Some("run") => cmd_run(args),
+        Some("verify") => cmd_verify(args),
+        Some("bench") => cmd_bench(args),
+        Some("gen") => cmd_gen(args),
+        Some("fuzz") => cmd_fuzz(args),
+        Some("diff") => cmd_diff(args),
+        Some("help") | None => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some(command) => Err(Error::new(format!("Unknown command: {}\n\n{}", command, USAGE))),
+    }
+}
+
+fn main() {
+    if let Err(err) = run(Args::from_env()) {
+        eprintln!("error: {}", err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,45 @@
+use std::any::Any;
+
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Solution, Solver};
+
+/// Solves every part of `S` against stdin and prints the answers.
+pub fn run<S: Solution>() -> Result<()> {
+    let input = Input::from_stdin()?;
+    let parsed = S::parse(input.lines()).map_err(|err| input.locate(err))?;
+
+    println!("Part1: {}", S::part1(&parsed).map_err(|err| input.locate(err))?);
+    if S::PARTS > 1 {
+        println!("Part2: {}", S::part2(&parsed).map_err(|err| input.locate(err))?);
+    }
+
+    Ok(())
+}
+
+/// Looks up the solver for `day`.
+pub fn find_solver(day: u32) -> Result<&'static dyn Solver> {
+    crate::solver(day).ok_or_else(|| Error::new(format!("No solution for day {}", day)))
+}
+
+/// Parses `input`, pointing any error at where it came from.
+pub fn parse_input(solver: &dyn Solver, input: &Input) -> Result<Box<dyn Any>> {
+    solver.parse(input.lines()).map_err(|err| input.locate(err))
+}
+
+/// Solves one part of an already parsed input.
+pub fn solve_part(solver: &dyn Solver, parsed: &dyn Any, part: u8) -> Result<Answer> {
+    match part {
+        1 => solver.part1(parsed),
+        2 if solver.parts() >= 2 => solver.part2(parsed),
+        _ => Err(Error::new(format!("Day {} has no part {}", solver.day(), part))),
+    }
+}
+
+/// The parts of `solver` to run: just `part` if given, otherwise all of them.
+pub fn parts(solver: &dyn Solver, part: Option<u8>) -> Vec<u8> {
+    match part {
+        Some(part) => vec![part],
+        None => (1..=solver.parts()).collect(),
+    }
+}
@@ -0,0 +1,171 @@
+use std::any::Any;
+use std::fmt::{self, Display};
+
+use crate::diff::Implementation;
+use crate::error::{Error, Result};
+use crate::generate::Generated;
+use crate::rng::Rng;
+
+/// The answer to one part of a puzzle.
+///
+/// Integer answers compare equal regardless of which variant holds them, so
+/// `Answer::Int(5) == Answer::BigInt(5)`.
+#[derive(Debug, Clone)]
+pub enum Answer {
+    Int(i64),
+    BigInt(i128),
+    Str(String),
+}
+
+impl Answer {
+    fn as_i128(&self) -> Option<i128> {
+        match self {
+            Answer::Int(n) => Some(i128::from(*n)),
+            Answer::BigInt(n) => Some(*n),
+            Answer::Str(_) => None,
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::BigInt(n) => write!(f, "{}", n),
+            Answer::Str(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Answer::Str(a), Answer::Str(b)) => a == b,
+            _ => match (self.as_i128(), other.as_i128()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+macro_rules! answer_from_int {
+    ($($t:ty),+) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                match i64::try_from(n) {
+                    Ok(n) => Answer::Int(n),
+                    Err(_) => Answer::BigInt(n as i128),
+                }
+            }
+        })+
+    };
+}
+
+answer_from_int!(i32, u32, i64, u64, usize);
+
+impl From<i128> for Answer {
+    fn from(n: i128) -> Self {
+        match i64::try_from(n) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::BigInt(n),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Str(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Str(s.to_string())
+    }
+}
+
+/// A day's puzzle: parse the input once, then solve both parts from it.
+pub trait Solution {
+    const DAY: u32;
+
+    /// Number of parts the puzzle has, or has been solved. Day 25 only has
+    /// the one.
+    const PARTS: u8 = 2;
+
+    type Parsed: 'static;
+
+    /// Other implementations of the parts, checked against this one by
+    /// `aoc diff`.
+    const IMPLEMENTATIONS: &'static [Implementation] = &[];
+
+    fn parse(lines: &[String]) -> Result<Self::Parsed>;
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer>;
+
+    fn part2(_parsed: &Self::Parsed) -> Result<Answer> {
+        Err(Error::new(format!("Day {} has no part 2", Self::DAY)))
+    }
+
+    /// A random valid input with about `size` entries, and its answers.
+    fn generate(_rng: &mut Rng, _size: usize) -> Option<Generated> {
+        None
+    }
+}
+
+/// Object safe view of a [`Solution`], so tooling can hold every day in one
+/// list. The parsed input is passed around as `dyn Any` and handed back to
+/// the solution it came from.
+pub trait Solver: Sync {
+    fn day(&self) -> u32;
+
+    fn parts(&self) -> u8;
+
+    fn parse(&self, lines: &[String]) -> Result<Box<dyn Any>>;
+
+    fn part1(&self, parsed: &dyn Any) -> Result<Answer>;
+
+    fn part2(&self, parsed: &dyn Any) -> Result<Answer>;
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<Generated>;
+
+    fn implementations(&self) -> &'static [Implementation];
+}
+
+fn downcast<S: Solution>(parsed: &dyn Any) -> Result<&S::Parsed> {
+    parsed
+        .downcast_ref::<S::Parsed>()
+        .ok_or_else(|| Error::new(format!("Parsed input does not belong to day {}", S::DAY)))
+}
+
+impl<S: Solution + Sync> Solver for S {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn parts(&self) -> u8 {
+        S::PARTS
+    }
+
+    fn parse(&self, lines: &[String]) -> Result<Box<dyn Any>> {
+        Ok(Box::new(S::parse(lines)?))
+    }
+
+    fn part1(&self, parsed: &dyn Any) -> Result<Answer> {
+        S::part1(downcast::<S>(parsed)?)
+    }
+
+    fn part2(&self, parsed: &dyn Any) -> Result<Answer> {
+        S::part2(downcast::<S>(parsed)?)
+    }
+
+    fn generate(&self, rng: &mut Rng, size: usize) -> Option<Generated> {
+        S::generate(rng, size)
+    }
+
+    fn implementations(&self) -> &'static [Implementation] {
+        S::IMPLEMENTATIONS
+    }
+}
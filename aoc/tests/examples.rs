@@ -0,0 +1,97 @@
+//! Runs every `dayN-test*.txt` example in the workspace root against its
+//! day's solver and checks the answers in the matching `.answers` file.
+//!
+//! Fixtures are found at run time, so dropping a new example and its answers
+//! next to the others is enough to add a test. Each fixture is reported as
+//! its own test, named after the file, and can be selected with a filter as
+//! usual: `cargo test --test examples day24`.
+
+use std::env;
+use std::panic;
+use std::path::Path;
+use std::process;
+
+use aoc::fixtures::{self, Fixture};
+
+fn run(fixture: &Fixture) -> Result<(), String> {
+    let verdicts = fixture.check().map_err(|err| err.to_string())?;
+
+    let failures = verdicts
+        .iter()
+        .filter(|(_, verdict)| verdict.is_failure())
+        .map(|(part, verdict)| format!("part{}: {}", part, verdict))
+        .collect::<Vec<_>>();
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures.join("\n"))
+    }
+}
+
+fn main() {
+    // Accept the filter and flags libtest takes, ignoring the flags.
+    let args: Vec<String> = env::args().skip(1).collect();
+    let list = args.iter().any(|arg| arg == "--list");
+    let exact = args.iter().any(|arg| arg == "--exact");
+    let filters: Vec<&String> = args.iter().filter(|arg| !arg.starts_with('-')).collect();
+
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    env::set_current_dir(&root).expect("workspace root");
+
+    let all = fixtures::discover(Path::new(".")).expect("fixtures");
+    let total = all.len();
+    let selected: Vec<Fixture> = all
+        .into_iter()
+        .filter(|fixture| {
+            let name = fixture.name();
+            filters.is_empty()
+                || filters.iter().any(|filter| {
+                    if exact { name == **filter } else { name.contains(filter.as_str()) }
+                })
+        })
+        .collect();
+
+    if list {
+        for fixture in selected.iter() {
+            println!("{}: test", fixture.name());
+        }
+        return;
+    }
+
+    let plural = if selected.len() == 1 { "" } else { "s" };
+    println!("\nrunning {} test{}", selected.len(), plural);
+
+    let mut failed = vec![];
+    for fixture in selected.iter() {
+        let result = panic::catch_unwind(|| run(fixture))
+            .unwrap_or_else(|_| Err("panicked".to_string()));
+
+        match result {
+            Ok(()) => println!("test {} ... ok", fixture.name()),
+            Err(message) => {
+                println!("test {} ... FAILED", fixture.name());
+                failed.push((fixture.name(), message));
+            }
+        }
+    }
+
+    if !failed.is_empty() {
+        println!("\nfailures:");
+        for (name, message) in failed.iter() {
+            println!("\n---- {} ----\n{}", name, message);
+        }
+    }
+
+    println!(
+        "\ntest result: {}. {} passed; {} failed; 0 ignored; 0 measured; {} filtered out\n",
+        if failed.is_empty() { "ok" } else { "FAILED" },
+        selected.len() - failed.len(),
+        failed.len(),
+        total - selected.len(),
+    );
+
+    if !failed.is_empty() {
+        process::exit(101);
+    }
+}